@@ -0,0 +1,50 @@
+use std::str;
+
+use long::graph::same_edges;
+use long::DeBruijnGraph;
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Problem};
+
+/// Constructing a De Bruijn Graph
+pub struct Dbru;
+
+impl Problem for Dbru {
+    type Input = Vec<fasta::Record>;
+    type Output = Vec<(String, String)>;
+
+    fn id(&self) -> &'static str {
+        "DBRU"
+    }
+
+    /// One (k+1)-mer per line, or FASTA
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_sequences(input, Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let graph = DeBruijnGraph::from_kmers(input.iter().map(|r| r.seq()), true)?;
+        Ok(graph
+            .adjacency_list()
+            .into_iter()
+            .map(|(from, to)| {
+                (
+                    str::from_utf8(from).unwrap().to_string(),
+                    str::from_utf8(to).unwrap().to_string(),
+                )
+            })
+            .collect())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|(from, to)| format!("({}, {})", from, to))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Any listing order of the edges is accepted
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        same_edges(expected, actual)
+    }
+}
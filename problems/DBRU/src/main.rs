@@ -0,0 +1,5 @@
+use dbru::Dbru;
+
+fn main() -> Result<(), rosalind_core::Error> {
+    rosalind_core::problem::run_main(&mut Dbru)
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use rosalind_core::{Error, Problem};
+
+/// Count the occurrences of each nucleotide in a DNA string
+///
+/// Arguments:
+/// * `line`: The DNA string to count, returned in `A C G T` order
+pub fn count_nucleotides(line: &str) -> [usize; 4] {
+    let mut counts = HashMap::from([('A', 0), ('C', 0), ('G', 0), ('T', 0)]);
+    for c in line.chars() {
+        if counts.contains_key(&c) {
+            *counts.get_mut(&c).unwrap() += 1;
+        }
+    }
+    [counts[&'A'], counts[&'C'], counts[&'G'], counts[&'T']]
+}
+
+/// Counting DNA Nucleotides
+pub struct Dna;
+
+impl Problem for Dna {
+    type Input = Vec<String>;
+    type Output = Vec<[usize; 4]>;
+
+    fn id(&self) -> &'static str {
+        "DNA"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(input.iter().map(|line| count_nucleotides(line)).collect())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|c| format!("{} {} {} {}", c[0], c[1], c[2], c[3]))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,92 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use rosalind_core::modular;
+use rosalind_core::recurrence::LinearRecurrence;
+use rosalind_core::{Error, Options, Problem};
+
+/// The number of rabbit pairs present after `n` months, if every mature
+///  pair produces a litter of `k` pairs each month. This is the order-2
+///  recurrence `F(n) = F(n-1) + k·F(n-2)` with `F(1) = F(2) = 1`.
+///
+/// Arguments:
+/// * `n`: The number of months, starting from a single newborn pair
+/// * `k`: The number of pairs in each litter
+/// * `modulo`: Reduce the answer modulo this value, if given
+pub fn fib(n: u64, k: usize, modulo: Option<&BigUint>) -> BigUint {
+    if n == 0 {
+        return BigUint::ZERO;
+    }
+    let one = BigUint::one();
+    LinearRecurrence::new(vec![one.clone(), BigUint::from(k)], vec![one.clone(), one])
+        .term(n - 1, modulo)
+}
+
+/// Rabbits and Recurrence Relations
+#[derive(Default)]
+pub struct Fib {
+    /// Report each answer modulo this value
+    pub modulo: Option<BigUint>,
+}
+
+impl Problem for Fib {
+    type Input = Vec<(u64, usize)>;
+    type Output = Vec<BigUint>;
+
+    fn id(&self) -> &'static str {
+        "FIB"
+    }
+
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.modulo = modular::take_modulo(options)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let mut queries = vec![];
+        for line in input.lines().filter(|l| !l.trim().is_empty()) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 2 {
+                return Err(format!("expected 'n k', found '{}'", line).into());
+            }
+            let n: u64 = parts[0].parse::<u64>()?;
+            let k: usize = parts[1].parse::<usize>()?;
+            queries.push((n, k));
+        }
+        Ok(queries)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(input
+            .into_iter()
+            .map(|(n, k)| fib(n, k, self.modulo.as_ref()))
+            .collect())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::Solver;
+
+    #[test]
+    fn sample_dataset() {
+        assert_eq!(Fib::default().run("5 3").unwrap(), "19");
+    }
+
+    #[test]
+    fn distant_months_are_reduced() {
+        let solver = Fib {
+            modulo: Some(BigUint::from(1_000_000u32)),
+        };
+        assert_eq!(solver.run("1000000000000000000 3").unwrap(), "761863");
+    }
+}
@@ -0,0 +1,126 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use rosalind_core::modular;
+use rosalind_core::recurrence::LinearRecurrence;
+use rosalind_core::{Error, Options, Problem};
+
+/// The number of rabbit pairs alive after `n` months, if every pair lives
+///  for `m` months and each mature pair produces one new pair per month.
+///  The births follow the order-`m` recurrence `B(n) = B(n-2) + ... + B(n-m)`,
+///  so its state vector `[B(n), ..., B(n-m+1)]` is the population of each
+///  age and the answer is its sum.
+///
+/// Arguments:
+/// * `n`: The number of months, starting from a single newborn pair
+/// * `m`: The lifespan of each pair in months
+/// * `modulo`: Reduce the answer modulo this value, if given
+pub fn fibd(n: u64, m: usize, modulo: Option<&BigUint>) -> Result<BigUint, Error> {
+    if m == 0 {
+        return Err("lifespan must be at least one month".into());
+    }
+    if n == 0 {
+        return Ok(BigUint::ZERO);
+    }
+    let mut coefficients = vec![BigUint::one(); m];
+    coefficients[0] = BigUint::zero();
+    // Shift the sequence so a single newborn pair is the last initial term
+    let mut initial = vec![BigUint::zero(); m];
+    initial[m - 1] = BigUint::one();
+    let births = LinearRecurrence::new(coefficients, initial);
+    let ages = births.state(n - 1 + (m as u64 - 1), modulo);
+    Ok(modular::reduce(ages.into_iter().sum(), modulo))
+}
+
+/// Mortal Fibonacci Rabbits
+#[derive(Default)]
+pub struct Fibd {
+    /// Report each answer modulo this value
+    pub modulo: Option<BigUint>,
+}
+
+impl Problem for Fibd {
+    type Input = Vec<(u64, usize)>;
+    type Output = Vec<BigUint>;
+
+    fn id(&self) -> &'static str {
+        "FIBD"
+    }
+
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.modulo = modular::take_modulo(options)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let mut queries = vec![];
+        for line in input.lines().filter(|l| !l.trim().is_empty()) {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 2 {
+                return Err(format!("expected 'n m', found '{}'", line).into());
+            }
+            let n: u64 = parts[0].parse::<u64>()?;
+            let m: usize = parts[1].parse::<usize>()?;
+            if m == 0 {
+                return Err(
+                    format!("lifespan must be at least one month, found '{}'", line).into(),
+                );
+            }
+            queries.push((n, m));
+        }
+        Ok(queries)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        input
+            .into_iter()
+            .map(|(n, m)| fibd(n, m, self.modulo.as_ref()))
+            .collect()
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::population::LeslieModel;
+    use rosalind_core::Solver;
+
+    #[test]
+    fn large_answers_are_exact() {
+        let solver = Fibd::default();
+        assert_eq!(solver.run("6 3").unwrap(), "4");
+        assert_eq!(solver.run("100 20").unwrap(), "353368918335207375428");
+    }
+
+    #[test]
+    fn answers_can_be_reduced() {
+        let solver = Fibd {
+            modulo: Some(BigUint::from(1_000_000u32)),
+        };
+        assert_eq!(solver.run("100 20").unwrap(), "375428");
+    }
+
+    #[test]
+    fn the_recurrence_agrees_with_the_population_model() {
+        for m in 1..6 {
+            let model = LeslieModel::<BigUint>::fibd(m).unwrap();
+            for n in 1..20 {
+                assert_eq!(fibd(n, m, None).unwrap(), model.total_at(n - 1, None));
+            }
+        }
+    }
+
+    #[test]
+    fn a_zero_lifespan_is_rejected() {
+        assert!(Fibd::default().run("6 0").is_err());
+        assert!(fibd(6, 0, None).is_err());
+    }
+}
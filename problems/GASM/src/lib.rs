@@ -0,0 +1,39 @@
+use std::str;
+
+use bio::alphabets::dna;
+use long::debruijn::{self, is_rotation};
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Problem};
+
+/// Genome Assembly Using Reads
+pub struct Gasm;
+
+impl Problem for Gasm {
+    type Input = Vec<fasta::Record>;
+    type Output = String;
+
+    fn id(&self) -> &'static str {
+        "GASM"
+    }
+
+    /// One read per line, or FASTA
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_sequences(input, Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let genome = debruijn::assemble_circular_double_stranded(&input)?;
+        Ok(str::from_utf8(&genome)?.to_string())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output.clone()
+    }
+
+    /// Either strand of the circular genome may be reported, from any
+    ///  starting point
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        let (expected, actual) = (expected.trim().as_bytes(), actual.trim().as_bytes());
+        is_rotation(expected, actual) || is_rotation(&dna::revcomp(expected), actual)
+    }
+}
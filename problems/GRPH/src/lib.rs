@@ -0,0 +1,56 @@
+use long::graph::same_edges;
+use long::OverlapGraph;
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Options, Problem};
+
+/// The overlap length GRPH asks for
+pub const DEFAULT_K: usize = 3;
+
+/// Overlap Graphs
+pub struct Grph {
+    /// The exact overlap length that makes an edge
+    pub k: usize,
+}
+
+impl Default for Grph {
+    fn default() -> Grph {
+        Grph { k: DEFAULT_K }
+    }
+}
+
+impl Problem for Grph {
+    type Input = Vec<fasta::Record>;
+    type Output = String;
+
+    fn id(&self) -> &'static str {
+        "GRPH"
+    }
+
+    /// `--k <k>` changes the overlap length from 3
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        if let Some(k) = options.take::<usize>("k")? {
+            if k == 0 {
+                return Err("--k must be at least 1".into());
+            }
+            self.k = k;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_fasta(input.as_bytes(), Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(OverlapGraph::new(&input, self.k).to_grph())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output.clone()
+    }
+
+    /// Any listing order of the edges is accepted
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        same_edges(expected, actual)
+    }
+}
@@ -0,0 +1,102 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+
+use rosalind_core::genetics::{Cross, Dominance};
+use rosalind_core::rational;
+use rosalind_core::{Error, Options, Problem};
+
+/// The parental genotype pairings, in the order IEV lists their counts
+pub const COUPLES: [(&str, &str); 6] = [
+    ("AA", "AA"),
+    ("AA", "Aa"),
+    ("AA", "aa"),
+    ("Aa", "Aa"),
+    ("Aa", "aa"),
+    ("aa", "aa"),
+];
+
+/// The number of children each IEV couple has
+pub const OFFSPRING_PER_COUPLE: u64 = 2;
+
+/// The expected number of offspring with the dominant phenotype
+///
+/// Arguments:
+/// * `counts`: The number of couples of each pairing in `COUPLES`
+pub fn iev(counts: Vec<u32>) -> Result<BigRational, Error> {
+    if counts.len() != COUPLES.len() {
+        return Err(format!(
+            "expected {} couple counts, found {}",
+            COUPLES.len(),
+            counts.len()
+        )
+        .into());
+    }
+    let mut total = BigRational::zero();
+    for (count, (mother, father)) in counts.iter().zip(COUPLES.iter()) {
+        let cross = Cross::new(mother.parse()?, father.parse()?)?;
+        let dominant = cross.expected_offspring(OFFSPRING_PER_COUPLE, |g| {
+            g.phenotype(&[Dominance::Complete]).is_dominant(0)
+        });
+        total += dominant * BigInt::from(*count);
+    }
+    Ok(total)
+}
+
+/// Calculating Expected Offspring
+#[derive(Default)]
+pub struct Iev {
+    /// Print each answer to this many decimal places
+    pub digits: Option<usize>,
+}
+
+impl Problem for Iev {
+    type Input = Vec<Vec<u32>>;
+    type Output = Vec<BigRational>;
+
+    fn id(&self) -> &'static str {
+        "IEV"
+    }
+
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.digits = rational::take_digits(options)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let mut couples = vec![];
+        for line in input.lines().filter(|l| !l.trim().is_empty()) {
+            let counts = line
+                .split_whitespace()
+                .map(|x| x.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()?;
+            couples.push(counts);
+        }
+        Ok(couples)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        input.into_iter().map(iev).collect()
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|v| rational::format_decimal(v, self.digits))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Answers are compared as exact decimals, so `3.5` and `3.50` agree
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        let e: Vec<&str> = expected.split_whitespace().collect();
+        let a: Vec<&str> = actual.split_whitespace().collect();
+        e.len() == a.len()
+            && e.iter().zip(a.iter()).all(|(e, a)| {
+                match (rational::parse_decimal(e), rational::parse_decimal(a)) {
+                    (Ok(e), Ok(a)) => e == a,
+                    _ => false,
+                }
+            })
+    }
+}
@@ -0,0 +1,281 @@
+//! Permutations, signed permutations, k-permutations and k-subsets, each
+//!  generated in lexicographic order, with their counts available as big
+//!  integers without enumerating anything.
+//!
+//! Unsigned arrangements are of the indices `0..n`, so they can pick from
+//!  any slice; signed permutations are of the values `1..=n` since a sign
+//!  on zero would be lost.
+
+use std::iter::FusedIterator;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// `n!`, the number of permutations of `n` items
+pub fn permutation_count(n: usize) -> BigUint {
+    k_permutation_count(n, n)
+}
+
+/// `2^n · n!`, the number of signed permutations of `n` items
+pub fn signed_permutation_count(n: usize) -> BigUint {
+    permutation_count(n) << n
+}
+
+/// `n! / (n-k)!`, the number of ordered selections of `k` of `n` items, or
+///  zero if `k > n`
+///
+/// Arguments:
+/// * `n`: The number of items to choose from
+/// * `k`: The number of items in each selection
+pub fn k_permutation_count(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    ((n - k + 1)..=n).fold(BigUint::one(), |acc, i| acc * i)
+}
+
+/// `n choose k`, the number of `k`-item subsets of `n` items, or zero if
+///  `k > n`
+///
+/// Arguments:
+/// * `n`: The number of items to choose from
+/// * `k`: The number of items in each subset
+pub fn subset_count(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    let k = k.min(n - k);
+    // Each partial product is itself a binomial coefficient, so the
+    //  division is always exact
+    (0..k).fold(BigUint::one(), |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Step `current` to the lexicographically next arrangement of distinct
+///  symbols from `0..symbols`, where no two symbols may share a `key`.
+///  Returns false, leaving `current` unspecified, once it was the last.
+fn advance<F>(current: &mut [usize], symbols: usize, keys: usize, key: F) -> bool
+where
+    F: Fn(usize) -> usize,
+{
+    let mut used = vec![false; keys];
+    for &s in current.iter() {
+        used[key(s)] = true;
+    }
+    for i in (0..current.len()).rev() {
+        used[key(current[i])] = false;
+        let bigger = (current[i] + 1..symbols).find(|&s| !used[key(s)]);
+        if let Some(s) = bigger {
+            current[i] = s;
+            used[key(s)] = true;
+            // Fill the rest with the smallest symbols still free, marking
+            //  each as it goes since several symbols may share a key
+            let mut s = 0;
+            for slot in current[i + 1..].iter_mut() {
+                while used[key(s)] {
+                    s += 1;
+                }
+                *slot = s;
+                used[key(s)] = true;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Every ordered selection of `k` distinct indices from `0..n`, in
+///  lexicographic order. With `k == n` these are the permutations of `n`
+///  (Rosalind PERM).
+#[derive(Clone, Debug)]
+pub struct KPermutations {
+    n: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl KPermutations {
+    /// Arguments:
+    /// * `n`: The number of indices to choose from
+    /// * `k`: The length of each selection; there are none if `k > n`
+    pub fn new(n: usize, k: usize) -> KPermutations {
+        KPermutations {
+            n,
+            next: if k <= n { Some((0..k).collect()) } else { None },
+        }
+    }
+}
+
+/// Every permutation of the indices `0..n`, in lexicographic order
+///
+/// Arguments:
+/// * `n`: The number of indices to permute
+pub fn permutations(n: usize) -> KPermutations {
+    KPermutations::new(n, n)
+}
+
+impl Iterator for KPermutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+        let mut following = current.clone();
+        if advance(&mut following, self.n, self.n, |s| s) {
+            self.next = Some(following);
+        }
+        Some(current)
+    }
+}
+
+impl FusedIterator for KPermutations {}
+
+/// Every permutation of `1..=n` with each entry made positive or negative,
+///  in numeric lexicographic order (Rosalind SIGN)
+#[derive(Clone, Debug)]
+pub struct SignedPermutations {
+    n: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl SignedPermutations {
+    /// Arguments:
+    /// * `n`: The number of values to permute
+    pub fn new(n: usize) -> SignedPermutations {
+        SignedPermutations {
+            n,
+            next: Some((0..n).collect()),
+        }
+    }
+
+    /// The signed value of symbol `s`, where symbols `0..2n` stand for
+    ///  `-n..=-1` then `1..=n`
+    fn value(&self, s: usize) -> isize {
+        if s < self.n {
+            -((self.n - s) as isize)
+        } else {
+            (s - self.n + 1) as isize
+        }
+    }
+}
+
+impl Iterator for SignedPermutations {
+    type Item = Vec<isize>;
+
+    fn next(&mut self) -> Option<Vec<isize>> {
+        let current = self.next.take()?;
+        let mut following = current.clone();
+        let n = self.n;
+        let magnitude = |s: usize| if s < n { n - 1 - s } else { s - n };
+        if advance(&mut following, 2 * n, n, magnitude) {
+            self.next = Some(following);
+        }
+        Some(current.into_iter().map(|s| self.value(s)).collect())
+    }
+}
+
+impl FusedIterator for SignedPermutations {}
+
+/// Every `k`-element subset of the indices `0..n`, each listed in
+///  ascending order, in lexicographic order
+#[derive(Clone, Debug)]
+pub struct Subsets {
+    n: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl Subsets {
+    /// Arguments:
+    /// * `n`: The number of indices to choose from
+    /// * `k`: The size of each subset; there are none if `k > n`
+    pub fn new(n: usize, k: usize) -> Subsets {
+        Subsets {
+            n,
+            next: if k <= n { Some((0..k).collect()) } else { None },
+        }
+    }
+}
+
+impl Iterator for Subsets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+        let k = current.len();
+        // The rightmost entry that can still grow is the one below its
+        //  largest possible value, `n - k + i`
+        if let Some(i) = (0..k).rev().find(|&i| current[i] < self.n - k + i) {
+            let mut following = current.clone();
+            following[i] += 1;
+            for j in i + 1..k {
+                following[j] = following[j - 1] + 1;
+            }
+            self.next = Some(following);
+        }
+        Some(current)
+    }
+}
+
+impl FusedIterator for Subsets {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_permutations_of_three() {
+        let all: Vec<Vec<usize>> = KPermutations::new(3, 2).collect();
+        assert_eq!(all, [[0, 1], [0, 2], [1, 0], [1, 2], [2, 0], [2, 1]]);
+        assert_eq!(k_permutation_count(3, 2), BigUint::from(6u8));
+        assert_eq!(KPermutations::new(2, 3).count(), 0);
+        assert_eq!(k_permutation_count(2, 3), BigUint::zero());
+        assert_eq!(permutations(0).collect::<Vec<_>>(), [Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn subsets_of_four() {
+        let all: Vec<Vec<usize>> = Subsets::new(4, 2).collect();
+        assert_eq!(all, [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]);
+        assert_eq!(subset_count(4, 2), BigUint::from(6u8));
+        assert_eq!(Subsets::new(4, 5).count(), 0);
+    }
+
+    #[test]
+    fn signed_permutations_of_two() {
+        let all: Vec<Vec<isize>> = SignedPermutations::new(2).collect();
+        assert_eq!(
+            all,
+            [
+                [-2, -1],
+                [-2, 1],
+                [-1, -2],
+                [-1, 2],
+                [1, -2],
+                [1, 2],
+                [2, -1],
+                [2, 1]
+            ]
+        );
+        assert_eq!(signed_permutation_count(2), BigUint::from(8u8));
+    }
+
+    #[test]
+    fn counts_match_enumeration() {
+        assert_eq!(permutation_count(5), BigUint::from(120u8));
+        assert_eq!(permutations(5).count(), 120);
+        assert_eq!(subset_count(30, 15), BigUint::from(155_117_520u32));
+        for n in 0..=6 {
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    BigUint::from(KPermutations::new(n, k).count()),
+                    k_permutation_count(n, k)
+                );
+                assert_eq!(
+                    BigUint::from(Subsets::new(n, k).count()),
+                    subset_count(n, k)
+                );
+            }
+            assert_eq!(
+                BigUint::from(SignedPermutations::new(n).count()),
+                signed_permutation_count(n)
+            );
+        }
+    }
+}
@@ -0,0 +1,615 @@
+//! Enumeration of words over an ordered alphabet, in lexicographic,
+//!  colexicographic, Gray-code or dictionary order.
+//!
+//! Every word is identified by its rank, i.e. its position in the ordering,
+//!  so the enumerator only ever advances a counter and each `Word` is a
+//!  small `Copy` handle that reads its symbols straight out of the rank.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+use std::ops::Range;
+use std::str::FromStr;
+
+use rosalind_core::Error;
+
+/// The order in which a `Lexicon` lists its words
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Words of one length, compared from the first symbol (Rosalind LEXF)
+    #[default]
+    Lexicographic,
+    /// Words of one length, compared from the last symbol
+    Colexicographic,
+    /// Words of one length in reflected Gray-code order, so consecutive
+    ///  words differ by one step at a single position
+    Gray,
+    /// Every word of length 1 up to the given length, with each word
+    ///  immediately followed by its extensions (Rosalind LEXV)
+    Dictionary,
+}
+
+impl FromStr for Order {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Order, Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "lex" | "lexf" => Ok(Order::Lexicographic),
+            "colex" => Ok(Order::Colexicographic),
+            "gray" => Ok(Order::Gray),
+            "lexv" | "dictionary" => Ok(Order::Dictionary),
+            _ => Err(format!(
+                "unknown order '{}', expected one of lex, colex, gray or lexv",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+/// A single word of a `Lexicon`, borrowed from the alphabet it was built from
+#[derive(Clone, Copy, Debug)]
+pub struct Word<'a, T> {
+    alphabet: &'a [T],
+    order: Order,
+    len: usize,
+    rank: usize,
+    span: usize,
+}
+
+impl<'a, T> Word<'a, T> {
+    /// The position of this word in its lexicon's ordering, starting from 0
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// The number of symbols in the word
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this is the empty word
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The alphabet index of each symbol, from first to last
+    pub fn indices(&self) -> Indices {
+        Indices {
+            order: self.order,
+            rank: self.rank,
+            base: self.alphabet.len(),
+            divisor: self.span,
+            remaining: self.len,
+            reflected: false,
+        }
+    }
+
+    /// The symbols of the word, from first to last
+    pub fn symbols(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let alphabet = self.alphabet;
+        self.indices().map(move |i| &alphabet[i])
+    }
+
+    /// Copy the symbols of the word into `buf`, which must be exactly
+    ///  `len()` long, so a single buffer can be reused across a whole
+    ///  enumeration
+    pub fn write_to(&self, buf: &mut [T])
+    where
+        T: Clone,
+    {
+        assert_eq!(buf.len(), self.len, "buffer length must match word length");
+        for (slot, symbol) in buf.iter_mut().zip(self.symbols()) {
+            *slot = symbol.clone();
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Word<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for symbol in self.symbols() {
+            write!(f, "{}", symbol)?;
+        }
+        Ok(())
+    }
+}
+
+/// The alphabet indices of a `Word`'s symbols, from first to last
+#[derive(Clone, Debug)]
+pub struct Indices {
+    order: Order,
+    rank: usize,
+    base: usize,
+    divisor: usize,
+    remaining: usize,
+    reflected: bool,
+}
+
+impl Iterator for Indices {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let index = match self.order {
+            Order::Lexicographic => {
+                let index = (self.rank / self.divisor) % self.base;
+                self.divisor = (self.divisor / self.base).max(1);
+                index
+            }
+            Order::Colexicographic => {
+                let index = (self.rank / self.divisor) % self.base;
+                self.divisor = self.divisor.saturating_mul(self.base);
+                index
+            }
+            Order::Gray => {
+                let digit = (self.rank / self.divisor) % self.base;
+                self.divisor = (self.divisor / self.base).max(1);
+                let index = if self.reflected {
+                    self.base - 1 - digit
+                } else {
+                    digit
+                };
+                // The rest of the word runs backwards whenever an odd number
+                //  of the symbols so far have an odd index
+                self.reflected ^= index % 2 == 1;
+                index
+            }
+            Order::Dictionary => {
+                // `divisor` is the number of words sharing the prefix so
+                //  far, the prefix itself included, so the quotient picks the
+                //  next symbol and the remainder (less the prefix) is the
+                //  rank within that symbol's subtree
+                let index = self.rank / self.divisor;
+                self.rank = (self.rank % self.divisor).saturating_sub(1);
+                self.divisor = (self.divisor - 1) / self.base;
+                index
+            }
+        };
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Indices {}
+
+/// Every word up to a given length over an ordered alphabet, in one of the
+///  `Order`s. The alphabet's order is the order of the slice it is built
+///  from, so symbols need not implement `Ord`.
+#[derive(Clone, Debug)]
+pub struct Lexicon<'a, T> {
+    alphabet: &'a [T],
+    order: Order,
+    len: usize,
+    total: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Lexicon<'a, T> {
+    /// Enumerate every word of length `len` over `alphabet`. Returns `None`
+    ///  if there are more words than fit in a `usize`.
+    ///
+    /// An empty alphabet has no words of positive length, and every alphabet
+    ///  has exactly one word of length zero.
+    ///
+    /// Arguments:
+    /// * `alphabet`: The symbols to build words from, in their sort order
+    /// * `len`: The length of every word
+    pub fn new(alphabet: &'a [T], len: usize) -> Option<Lexicon<'a, T>> {
+        Lexicon::with_order(alphabet, len, Order::Lexicographic)
+    }
+
+    /// Enumerate words over `alphabet` in the given order. Every order but
+    ///  `Dictionary` lists the words of length exactly `len`; `Dictionary`
+    ///  lists those of length 1 through `len`, or just the empty word if
+    ///  `len` is zero. Returns `None` if there are more words than fit in a
+    ///  `usize`.
+    ///
+    /// Arguments:
+    /// * `alphabet`: The symbols to build words from, in their sort order
+    /// * `len`: The length of every word, or the longest for `Dictionary`
+    /// * `order`: The order to list the words in
+    pub fn with_order(alphabet: &'a [T], len: usize, order: Order) -> Option<Lexicon<'a, T>> {
+        let base = alphabet.len();
+        let count = match order {
+            Order::Dictionary if len == 0 => 1,
+            Order::Dictionary => {
+                (1..=len)
+                    .try_fold((0usize, 1usize), |(sum, power), _| {
+                        let power = power.checked_mul(base)?;
+                        Some((sum.checked_add(power)?, power))
+                    })?
+                    .0
+            }
+            _ => base.checked_pow(u32::try_from(len).ok()?)?,
+        };
+        Some(Lexicon {
+            alphabet,
+            order,
+            len,
+            total: count,
+            front: 0,
+            back: count,
+        })
+    }
+
+    /// The number of words in the full ordering, regardless of how far
+    ///  this enumerator has advanced
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The alphabet the words are built from
+    pub fn alphabet(&self) -> &'a [T] {
+        self.alphabet
+    }
+
+    /// The order the words are listed in
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    /// The length of every word, or of the longest words in `Dictionary`
+    ///  order
+    pub fn word_len(&self) -> usize {
+        self.len
+    }
+
+    /// The number of `Dictionary` words starting with a given symbol, that
+    ///  symbol alone included
+    fn subtree_size(&self) -> usize {
+        self.total.checked_div(self.alphabet.len()).unwrap_or(0)
+    }
+
+    fn word(&self, rank: usize) -> Word<'a, T> {
+        let base = self.alphabet.len();
+        let (len, span) = match self.order {
+            Order::Lexicographic | Order::Gray => (
+                self.len,
+                base.saturating_pow(self.len.saturating_sub(1) as u32),
+            ),
+            Order::Colexicographic => (self.len, 1),
+            Order::Dictionary if self.len == 0 => (0, 1),
+            Order::Dictionary => {
+                let span = self.subtree_size();
+                let (mut len, mut rest, mut size) = (1, rank % span, span);
+                while rest > 0 {
+                    size = (size - 1) / base;
+                    rest = (rest - 1) % size;
+                    len += 1;
+                }
+                (len, span)
+            }
+        };
+        Word {
+            alphabet: self.alphabet,
+            order: self.order,
+            len,
+            rank,
+            span,
+        }
+    }
+
+    /// The word at position `rank` in the full ordering, if there is one
+    ///
+    /// Arguments:
+    /// * `rank`: The 0-based position of the word
+    pub fn unrank(&self, rank: usize) -> Option<Word<'a, T>> {
+        if rank < self.total {
+            Some(self.word(rank))
+        } else {
+            None
+        }
+    }
+
+    /// The position of a word in the full ordering, given the alphabet index
+    ///  of each of its symbols. Returns `None` if the word has the wrong
+    ///  length or an index is outside the alphabet.
+    ///
+    /// Arguments:
+    /// * `indices`: The alphabet index of each symbol, first to last
+    pub fn rank_indices(&self, indices: &[usize]) -> Option<usize> {
+        let base = self.alphabet.len();
+        let fits = match self.order {
+            Order::Dictionary if self.len == 0 => indices.is_empty(),
+            Order::Dictionary => (1..=self.len).contains(&indices.len()),
+            _ => indices.len() == self.len,
+        };
+        if !fits || indices.iter().any(|&i| i >= base) {
+            return None;
+        }
+        let rank = match self.order {
+            Order::Lexicographic => indices.iter().fold(0, |rank, &i| rank * base + i),
+            Order::Colexicographic => indices.iter().rev().fold(0, |rank, &i| rank * base + i),
+            Order::Gray => {
+                let mut reflected = false;
+                indices.iter().fold(0, |rank, &i| {
+                    let digit = if reflected { base - 1 - i } else { i };
+                    reflected ^= i % 2 == 1;
+                    rank * base + digit
+                })
+            }
+            Order::Dictionary if indices.is_empty() => 0,
+            Order::Dictionary => {
+                let mut size = self.subtree_size();
+                let mut rank = indices.len() - 1;
+                for &i in indices {
+                    rank += i * size;
+                    size = (size - 1) / base;
+                }
+                rank
+            }
+        };
+        Some(rank)
+    }
+
+    /// The position of a word in the full ordering. Returns `None` if the
+    ///  word has the wrong length or uses a symbol outside the alphabet.
+    ///
+    /// Arguments:
+    /// * `word`: The symbols of the word, first to last
+    pub fn rank(&self, word: &[T]) -> Option<usize>
+    where
+        T: Hash + Eq,
+    {
+        // A symbol listed twice is read as its first position
+        let mut positions = HashMap::with_capacity(self.alphabet.len());
+        for (i, symbol) in self.alphabet.iter().enumerate() {
+            positions.entry(symbol).or_insert(i);
+        }
+        let indices = word
+            .iter()
+            .map(|symbol| positions.get(symbol).copied())
+            .collect::<Option<Vec<usize>>>()?;
+        self.rank_indices(&indices)
+    }
+
+    /// Move the front of the enumerator so the next word yielded is the one
+    ///  at `rank`, or exhaust it if `rank` is past the back
+    ///
+    /// Arguments:
+    /// * `rank`: The 0-based position to continue from
+    pub fn seek(&mut self, rank: usize) {
+        self.front = rank.min(self.back);
+    }
+
+    /// An enumerator over just the words with ranks in `ranks`
+    ///
+    /// Arguments:
+    /// * `ranks`: The half-open range of positions to enumerate
+    pub fn range(&self, ranks: Range<usize>) -> Lexicon<'a, T> {
+        let back = ranks.end.min(self.total);
+        Lexicon {
+            front: ranks.start.min(back),
+            back,
+            ..*self
+        }
+    }
+
+    /// Split the full ordering into `count` contiguous enumerators of
+    ///  near-equal size, e.g. to hand one to each thread
+    ///
+    /// Arguments:
+    /// * `count`: The number of shards, which must be non-zero
+    pub fn shards(&self, count: usize) -> Vec<Lexicon<'a, T>> {
+        assert!(count > 0, "cannot split a lexicon into zero shards");
+        let (size, extra) = (self.total / count, self.total % count);
+        let mut start = 0;
+        (0..count)
+            .map(|i| {
+                let end = start + size + usize::from(i < extra);
+                let shard = self.range(start..end);
+                start = end;
+                shard
+            })
+            .collect()
+    }
+}
+
+impl<'a, T> Iterator for Lexicon<'a, T> {
+    type Item = Word<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.word(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+}
+
+impl<T> DoubleEndedIterator for Lexicon<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.word(self.back))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n).max(self.front);
+        self.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Lexicon<'_, T> {}
+
+impl<T> FusedIterator for Lexicon<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERS: [Order; 4] = [
+        Order::Lexicographic,
+        Order::Colexicographic,
+        Order::Gray,
+        Order::Dictionary,
+    ];
+
+    fn words(alphabet: &[char], len: usize, order: Order) -> Vec<String> {
+        Lexicon::with_order(alphabet, len, order)
+            .unwrap()
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn orders_of_a_small_alphabet() {
+        let abc = ['a', 'b', 'c'];
+        assert_eq!(
+            words(&abc, 2, Order::Lexicographic),
+            ["aa", "ab", "ac", "ba", "bb", "bc", "ca", "cb", "cc"]
+        );
+        assert_eq!(
+            words(&abc, 2, Order::Colexicographic),
+            ["aa", "ba", "ca", "ab", "bb", "cb", "ac", "bc", "cc"]
+        );
+        assert_eq!(
+            words(&abc, 2, Order::Gray),
+            ["aa", "ab", "ac", "bc", "bb", "ba", "ca", "cb", "cc"]
+        );
+        assert_eq!(
+            words(&abc, 2, Order::Dictionary),
+            ["a", "aa", "ab", "ac", "b", "ba", "bb", "bc", "c", "ca", "cb", "cc"]
+        );
+    }
+
+    #[test]
+    fn rosalind_lexv_sample() {
+        let lexv = words(&['D', 'N', 'A'], 3, Order::Dictionary);
+        assert_eq!(lexv.len(), 39);
+        assert_eq!(
+            lexv[..14],
+            [
+                "D", "DD", "DDD", "DDN", "DDA", "DN", "DND", "DNN", "DNA", "DA", "DAD", "DAN",
+                "DAA", "N"
+            ]
+        );
+        assert_eq!(lexv[38], "AAA");
+    }
+
+    #[test]
+    fn gray_code_steps_one_symbol_at_a_time() {
+        for base in 1..=4 {
+            let alphabet: Vec<usize> = (0..base).collect();
+            for len in 1..=4 {
+                let all: Vec<Vec<usize>> = Lexicon::with_order(&alphabet, len, Order::Gray)
+                    .unwrap()
+                    .map(|w| w.indices().collect())
+                    .collect();
+                for pair in all.windows(2) {
+                    let diffs: Vec<usize> = (0..len)
+                        .filter(|&i| pair[0][i] != pair[1][i])
+                        .map(|i| pair[0][i].abs_diff(pair[1][i]))
+                        .collect();
+                    assert_eq!(diffs, [1], "{:?} -> {:?}", pair[0], pair[1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rank_and_unrank_are_inverse() {
+        for order in ORDERS {
+            for base in 0..=3 {
+                let alphabet: Vec<u8> = (b'a'..).take(base).collect();
+                for len in 0..=4 {
+                    let lexicon = Lexicon::with_order(&alphabet, len, order).unwrap();
+                    let total = lexicon.total();
+                    assert_eq!(lexicon.clone().count(), total);
+                    for (r, word) in lexicon.clone().enumerate() {
+                        assert_eq!(word.rank(), r);
+                        let symbols: Vec<u8> = word.symbols().copied().collect();
+                        assert_eq!(lexicon.rank(&symbols), Some(r), "{:?} {:?}", order, symbols);
+                        let indices: Vec<usize> = word.indices().collect();
+                        assert_eq!(lexicon.rank_indices(&indices), Some(r));
+                        let mut buf = vec![0; word.len()];
+                        lexicon.unrank(r).unwrap().write_to(&mut buf);
+                        assert_eq!(buf, symbols);
+                    }
+                    assert!(lexicon.unrank(total).is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ranking_rejects_foreign_words() {
+        let lexicon = Lexicon::new(&['a', 'b'], 2).unwrap();
+        assert_eq!(lexicon.rank(&['a']), None);
+        assert_eq!(lexicon.rank(&['a', 'z']), None);
+        assert_eq!(lexicon.rank_indices(&[0, 2]), None);
+        let lexv = Lexicon::with_order(&['a', 'b'], 2, Order::Dictionary).unwrap();
+        assert_eq!(lexv.rank(&[]), None);
+        assert_eq!(lexv.rank(&['a', 'b', 'a']), None);
+    }
+
+    #[test]
+    fn empty_alphabets_and_lengths() {
+        let none: [char; 0] = [];
+        for order in ORDERS {
+            assert_eq!(words(&none, 0, order), [""], "{:?}", order);
+            assert_eq!(words(&['a', 'b'], 0, order), [""], "{:?}", order);
+            assert!(words(&none, 3, order).is_empty(), "{:?}", order);
+        }
+        let lexv = Lexicon::with_order(&['a', 'b'], 0, Order::Dictionary).unwrap();
+        assert_eq!(lexv.rank(&[]), Some(0));
+        assert!(lexv.unrank(0).unwrap().is_empty());
+        assert!(Lexicon::new(&['a', 'b'], 64).is_none());
+        assert!(Lexicon::with_order(&['a', 'b'], 64, Order::Dictionary).is_none());
+    }
+
+    #[test]
+    fn seeking_and_iterating_from_both_ends() {
+        let alphabet = ['a', 'b', 'c'];
+        let mut lexicon = Lexicon::new(&alphabet, 3).unwrap();
+        lexicon.seek(25);
+        assert_eq!(lexicon.len(), 2);
+        assert_eq!(lexicon.next().unwrap().to_string(), "ccb");
+        lexicon.seek(100);
+        assert!(lexicon.next().is_none());
+
+        let mut lexicon = Lexicon::new(&alphabet, 3).unwrap();
+        assert_eq!(lexicon.nth_back(1).unwrap().to_string(), "ccb");
+        assert_eq!(lexicon.nth(2).unwrap().to_string(), "aac");
+        assert_eq!(lexicon.len(), 22);
+        assert!(lexicon.nth_back(30).is_none());
+        assert!(lexicon.next().is_none());
+    }
+
+    #[test]
+    fn shards_cover_the_ordering_once() {
+        let alphabet = ['a', 'b', 'c'];
+        for order in ORDERS {
+            let lexicon = Lexicon::with_order(&alphabet, 3, order).unwrap();
+            let all: Vec<usize> = lexicon.clone().map(|w| w.rank()).collect();
+            for count in [1, 2, 5, 64] {
+                let shards = lexicon.shards(count);
+                assert_eq!(shards.len(), count);
+                let sizes: Vec<usize> = shards.iter().map(|s| s.len()).collect();
+                assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+                let joined: Vec<usize> = shards.into_iter().flatten().map(|w| w.rank()).collect();
+                assert_eq!(joined, all);
+            }
+        }
+    }
+}
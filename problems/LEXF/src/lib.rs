@@ -0,0 +1,100 @@
+pub mod arrangements;
+pub mod lexicon;
+
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+
+use rosalind_core::{Error, Options, Problem};
+
+pub use arrangements::{KPermutations, SignedPermutations, Subsets};
+pub use lexicon::{Lexicon, Order, Word};
+
+/// Enumerating k-mers Lexicographically, or with `--order lexv` every word
+///  up to length k in dictionary order (Rosalind LEXV)
+#[derive(Default)]
+pub struct Lexf {
+    pub order: Order,
+}
+
+impl Problem for Lexf {
+    type Input = (Vec<String>, usize);
+    type Output = Vec<String>;
+
+    fn id(&self) -> &'static str {
+        "LEXF"
+    }
+
+    /// `--order lex|colex|gray|lexv` picks the order to list words in
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        if let Some(order) = options.take("order")? {
+            self.order = order;
+        }
+        Ok(())
+    }
+
+    /// The first line lists the alphabet's symbols in sort order, separated
+    ///  by whitespace, and the second gives the word length
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let mut lines = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let alpha: Vec<String> = lines
+            .next()
+            .ok_or("missing alphabet line")?
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        let len = lines
+            .next()
+            .ok_or("missing word length")?
+            .parse::<usize>()?;
+        Ok((alpha, len))
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let (alpha, len) = input;
+        let lexicon =
+            Lexicon::with_order(&alpha, len, self.order).ok_or("too many words to enumerate")?;
+        Ok(lexicon.map(|w| w.to_string()).collect())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output.join("\n")
+    }
+}
+
+/// Write a count on the first line and then one arrangement per line, with
+///  its entries separated by spaces
+///
+/// Arguments:
+/// * `count`: The number of arrangements
+/// * `arrangements`: The arrangements to list
+pub fn format_listing<T: Display>(count: &BigUint, arrangements: &[Vec<T>]) -> String {
+    let mut lines = vec![count.to_string()];
+    lines.extend(arrangements.iter().map(|a| {
+        a.iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }));
+    lines.join("\n")
+}
+
+/// Whether two answers that give a count on the first line and then one
+///  arrangement per line agree, whatever order the arrangements are listed in
+///
+/// Arguments:
+/// * `expected`: The reference answer
+/// * `actual`: The answer to check
+pub fn same_listing(expected: &str, actual: &str) -> bool {
+    let listing = |s: &str| {
+        let mut lines = s
+            .trim()
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<&str>>().join(" "));
+        let count = lines.next();
+        let mut rest: Vec<String> = lines.collect();
+        rest.sort();
+        (count, rest)
+    };
+    listing(expected) == listing(actual)
+}
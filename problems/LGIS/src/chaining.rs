@@ -0,0 +1,211 @@
+//! Maximum-weight chaining of alignment anchors, the two-dimensional and
+//!  weighted generalisation of a longest increasing subsequence. Chaining
+//!  the anchors `(i, seq[i])` with unit weights and no gap penalty finds a
+//!  longest increasing subsequence of `seq`.
+
+/// A seed match between two sequences, such as a shared k-mer starting at
+///  `x` in one and `y` in the other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Anchor {
+    pub x: usize,
+    pub y: usize,
+    pub weight: i64,
+}
+
+/// A chain of anchors, strictly increasing in both coordinates
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Chain {
+    /// The positions of the chained anchors in the input, in chain order
+    pub indices: Vec<usize>,
+    /// The total weight of the chain, less any gap penalties
+    pub score: i64,
+}
+
+/// A Fenwick tree over prefix maxima, remembering which anchor gave each
+struct MaxFenwick {
+    tree: Vec<Option<(i64, usize)>>,
+}
+
+impl MaxFenwick {
+    fn new(size: usize) -> MaxFenwick {
+        MaxFenwick {
+            tree: vec![None; size + 1],
+        }
+    }
+
+    /// Offer `value` for position `pos`
+    fn update(&mut self, pos: usize, value: (i64, usize)) {
+        let mut i = pos + 1;
+        while i < self.tree.len() {
+            if self.tree[i].is_none_or(|best| value.0 > best.0) {
+                self.tree[i] = Some(value);
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// The best value offered for any position before `end`
+    fn prefix_max(&self, end: usize) -> Option<(i64, usize)> {
+        let mut best: Option<(i64, usize)> = None;
+        let mut i = end;
+        while i > 0 {
+            if let Some(v) = self.tree[i] {
+                if best.is_none_or(|b| v.0 > b.0) {
+                    best = Some(v);
+                }
+            }
+            i -= i & i.wrapping_neg();
+        }
+        best
+    }
+}
+
+/// The maximum-score chain of `anchors` increasing strictly in both `x` and
+///  `y`, in O(n log n).
+///
+/// A linear gap penalty charges `penalty · ((x' - x) + (y' - y))` for each
+///  link from `(x, y)` to `(x', y')`. That cost splits into a term for each
+///  end, so the best predecessor is still a single prefix-maximum query.
+///
+/// The empty chain, with score zero, is returned unless some chain scores
+///  above zero, e.g. when no anchor has a positive weight.
+///
+/// Arguments:
+/// * `anchors`: The anchors to chain, in any order
+/// * `gap_penalty`: The cost per unit of gap along each link, if any
+pub fn chain(anchors: &[Anchor], gap_penalty: Option<i64>) -> Chain {
+    let penalty = gap_penalty.unwrap_or(0);
+    let diagonal = |a: &Anchor| (a.x + a.y) as i64;
+
+    let mut ys: Vec<usize> = anchors.iter().map(|a| a.y).collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    let mut order: Vec<usize> = (0..anchors.len()).collect();
+    order.sort_by_key(|&i| (anchors[i].x, anchors[i].y));
+
+    let mut fenwick = MaxFenwick::new(ys.len());
+    let mut score = vec![0i64; anchors.len()];
+    let mut predecessor: Vec<Option<usize>> = vec![None; anchors.len()];
+    // Anchors sharing an `x` cannot chain to each other, so a whole group
+    //  is scored before any of it is offered to later anchors
+    for group in order.chunk_by(|&a, &b| anchors[a].x == anchors[b].x) {
+        for &i in group {
+            let a = &anchors[i];
+            let rank = ys.partition_point(|&y| y < a.y);
+            score[i] = a.weight;
+            if let Some((best, j)) = fenwick.prefix_max(rank) {
+                let linked = a.weight + best - penalty * diagonal(a);
+                if linked > score[i] {
+                    score[i] = linked;
+                    predecessor[i] = Some(j);
+                }
+            }
+        }
+        for &i in group {
+            let a = &anchors[i];
+            let rank = ys.partition_point(|&y| y < a.y);
+            fenwick.update(rank, (score[i] + penalty * diagonal(a), i));
+        }
+    }
+
+    let end = match order.iter().copied().max_by_key(|&i| score[i]) {
+        Some(end) if score[end] > 0 => end,
+        _ => return Chain::default(),
+    };
+    let mut indices = vec![];
+    let mut next = Some(end);
+    while let Some(i) = next {
+        indices.push(i);
+        next = predecessor[i];
+    }
+    indices.reverse();
+    Chain {
+        indices,
+        score: score[end],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lis::{longest_increasing, Strictness};
+    use rosalind_core::testing::XorShift;
+
+    fn anchor(x: usize, y: usize, weight: i64) -> Anchor {
+        Anchor { x, y, weight }
+    }
+
+    #[test]
+    fn unit_weights_find_a_longest_increasing_subsequence() {
+        let mut rng = XorShift::new(0x853c49e6748fea9b);
+        for len in 0..60 {
+            let seq: Vec<usize> = (0..len).map(|_| rng.below(20)).collect();
+            let anchors: Vec<Anchor> = seq
+                .iter()
+                .enumerate()
+                .map(|(i, &y)| anchor(i, y, 1))
+                .collect();
+            let chained = chain(&anchors, None);
+            let lis = longest_increasing(&seq, Strictness::Strict);
+            assert_eq!(chained.indices.len(), lis.len(), "{:?}", seq);
+            assert_eq!(chained.score, lis.len() as i64);
+            assert!(chained
+                .indices
+                .windows(2)
+                .all(|w| seq[w[0]] < seq[w[1]] && w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn gap_penalty_breaks_distant_links() {
+        let anchors = [anchor(0, 0, 10), anchor(1, 1, 10), anchor(50, 50, 10)];
+        assert_eq!(
+            chain(&anchors, None),
+            Chain {
+                indices: vec![0, 1, 2],
+                score: 30
+            }
+        );
+        assert_eq!(
+            chain(&anchors, Some(1)),
+            Chain {
+                indices: vec![0, 1],
+                score: 18
+            }
+        );
+    }
+
+    #[test]
+    fn anchors_sharing_x_do_not_chain() {
+        let anchors = [anchor(0, 1, 1), anchor(0, 0, 1), anchor(1, 2, 1)];
+        let chained = chain(&anchors, None);
+        assert_eq!(chained.score, 2);
+        assert_eq!(chained.indices.len(), 2);
+        assert_eq!(chained.indices[1], 2);
+        let same_y = [anchor(0, 0, 1), anchor(1, 0, 1)];
+        assert_eq!(chain(&same_y, None).score, 1);
+    }
+
+    #[test]
+    fn zero_weights_give_the_empty_chain() {
+        let anchors = [anchor(0, 0, 0), anchor(1, 1, 0), anchor(2, 2, 0)];
+        assert_eq!(chain(&anchors, None), Chain::default());
+        assert_eq!(chain(&anchors, Some(1)), Chain::default());
+    }
+
+    #[test]
+    fn negative_weights_give_the_empty_chain() {
+        assert_eq!(chain(&[], None), Chain::default());
+        let anchors = [anchor(0, 0, -3), anchor(1, 1, -1)];
+        assert_eq!(chain(&anchors, None), Chain::default());
+        let mixed = [anchor(0, 0, -3), anchor(1, 1, 4), anchor(2, 2, -1)];
+        assert_eq!(
+            chain(&mixed, None),
+            Chain {
+                indices: vec![1],
+                score: 4
+            }
+        );
+    }
+}
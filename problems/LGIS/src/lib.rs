@@ -0,0 +1,183 @@
+pub mod chaining;
+pub mod lis;
+
+use num_bigint::BigUint;
+
+use rosalind_core::{Error, Options, Problem};
+
+pub use chaining::{chain, Anchor, Chain};
+pub use lis::{
+    all_longest_indices_by, count_longest_by, longest_decreasing, longest_decreasing_subsequence,
+    longest_increasing, longest_increasing_subsequence, longest_subsequence_by,
+    longest_subsequence_indices_by, Strictness, Subsequence,
+};
+
+/// Which longest subsequences LGIS reports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Listing {
+    /// One longest subsequence of each kind
+    #[default]
+    One,
+    /// Every longest subsequence of each kind
+    All,
+    /// Just the number of longest subsequences of each kind
+    Count,
+}
+
+/// The increasing and decreasing answers, in the form `Listing` asks for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    One(Vec<usize>, Vec<usize>),
+    All(Vec<Vec<usize>>, Vec<Vec<usize>>),
+    Count(BigUint, BigUint),
+}
+
+fn join(values: &[usize]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Longest Increasing Subsequence
+#[derive(Default)]
+pub struct Lgis {
+    pub listing: Listing,
+}
+
+impl Problem for Lgis {
+    type Input = Vec<usize>;
+    type Output = Answer;
+
+    fn id(&self) -> &'static str {
+        "LGIS"
+    }
+
+    /// `--all` lists every longest subsequence and `--count` just counts
+    ///  them
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.listing = match (options.take_flag("all")?, options.take_flag("count")?) {
+            (true, true) => return Err("--all and --count cannot be combined".into()),
+            (true, false) => Listing::All,
+            (false, true) => Listing::Count,
+            (false, false) => Listing::One,
+        };
+        Ok(())
+    }
+
+    /// The first line declares the permutation's length and the second
+    ///  lists it, and may be missing when the length is 0
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let mut lines = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let n = lines
+            .next()
+            .ok_or("missing permutation length")?
+            .parse::<usize>()?;
+        let vec_x = match lines.next() {
+            Some(line) => line
+                .split_whitespace()
+                .map(|n| n.parse::<usize>())
+                .collect::<Result<Vec<usize>, _>>()?,
+            None => vec![],
+        };
+        if vec_x.len() != n {
+            return Err(format!(
+                "declared a permutation of length {} but it has {} elements",
+                n,
+                vec_x.len()
+            )
+            .into());
+        }
+        Ok(vec_x)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let increasing = usize::cmp;
+        let decreasing = |a: &usize, b: &usize| b.cmp(a);
+        let strict = Strictness::Strict;
+        let values = |indices: Vec<usize>| indices.into_iter().map(|i| input[i]).collect();
+        Ok(match self.listing {
+            Listing::One => Answer::One(
+                longest_increasing_subsequence(&input),
+                longest_decreasing_subsequence(&input),
+            ),
+            Listing::All => Answer::All(
+                all_longest_indices_by(&input, strict, increasing)
+                    .into_iter()
+                    .map(values)
+                    .collect(),
+                all_longest_indices_by(&input, strict, decreasing)
+                    .into_iter()
+                    .map(values)
+                    .collect(),
+            ),
+            Listing::Count => Answer::Count(
+                count_longest_by(&input, strict, increasing),
+                count_longest_by(&input, strict, decreasing),
+            ),
+        })
+    }
+
+    /// Every subsequence goes on its own line; with `--all` the increasing
+    ///  ones are separated from the decreasing ones by a blank line
+    fn format(&self, output: &Self::Output) -> String {
+        match output {
+            Answer::One(i, d) => format!("{}\n{}", join(i), join(d)),
+            Answer::All(i, d) => {
+                let block = |all: &[Vec<usize>]| {
+                    all.iter()
+                        .map(|s| join(s))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                };
+                format!("{}\n\n{}", block(i), block(d))
+            }
+            Answer::Count(i, d) => format!("{}\n{}", i, d),
+        }
+    }
+
+    /// Any increasing/decreasing subsequence of the right length is accepted,
+    ///  since a permutation usually has more than one longest subsequence.
+    ///  Listings and counts are unique, so those must match exactly.
+    fn check(&self, input: &Self::Input, expected: &str, actual: &str) -> bool {
+        if self.listing != Listing::One {
+            return expected.trim() == actual.trim();
+        }
+        // An empty permutation's answer is two empty lines, so read exactly
+        //  two lines rather than trimming them away
+        let parse = |s: &str| -> Option<Vec<Vec<usize>>> {
+            let mut lines = s.lines();
+            (0..2)
+                .map(|_| {
+                    lines
+                        .next()
+                        .unwrap_or("")
+                        .split_whitespace()
+                        .map(|v| v.parse::<usize>().ok())
+                        .collect()
+                })
+                .collect()
+        };
+        let (expected, actual) = match (parse(expected), parse(actual)) {
+            (Some(e), Some(a)) => (e, a),
+            _ => return false,
+        };
+        is_subsequence(input, &actual[0])
+            && is_subsequence(input, &actual[1])
+            && actual[0].windows(2).all(|w| w[0] < w[1])
+            && actual[1].windows(2).all(|w| w[0] > w[1])
+            && actual[0].len() == expected[0].len()
+            && actual[1].len() == expected[1].len()
+    }
+}
+
+/// Whether `sub` can be obtained from `seq` by deleting elements
+///
+/// Arguments:
+/// * `seq`: The full sequence
+/// * `sub`: The candidate subsequence
+pub fn is_subsequence<T: PartialEq>(seq: &[T], sub: &[T]) -> bool {
+    let mut it = seq.iter();
+    sub.iter().all(|s| it.any(|x| x == s))
+}
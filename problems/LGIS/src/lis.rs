@@ -0,0 +1,358 @@
+//! Longest monotone subsequences by patience sorting, in O(n log n) for any
+//!  element type and ordering
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// Whether neighbouring elements of a subsequence may compare equal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Each element must come strictly after the one before it
+    #[default]
+    Strict,
+    /// Each element may also equal the one before it
+    NonStrict,
+}
+
+/// A subsequence, as positions in the original sequence and the elements
+///  found there
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subsequence<T> {
+    pub indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+impl<T> Subsequence<T> {
+    /// The number of elements in the subsequence
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether the subsequence has no elements
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// The elements of `seq` dealt onto patience-sorting piles, where pile `l`
+///  holds every element whose longest increasing subsequence ending there
+///  has length `l + 1`
+struct Piles {
+    piles: Vec<Vec<usize>>,
+    level: Vec<usize>,
+    /// The positions in the previous pile of the elements each element can
+    ///  follow. Values along a pile never increase, so these form a suffix
+    ///  of the pile as it stood when the element was dealt.
+    predecessors: Vec<Range<usize>>,
+}
+
+fn deal<T, F>(seq: &[T], strictness: Strictness, mut cmp: F) -> Piles
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut follows = |a: &T, b: &T| match cmp(a, b) {
+        Ordering::Less => true,
+        Ordering::Equal => strictness == Strictness::NonStrict,
+        Ordering::Greater => false,
+    };
+    let mut piles: Vec<Vec<usize>> = vec![];
+    let mut level = Vec::with_capacity(seq.len());
+    let mut predecessors = Vec::with_capacity(seq.len());
+    for (i, x) in seq.iter().enumerate() {
+        // The last elements of the piles increase, so the first pile whose
+        //  last element `x` cannot follow is where `x` goes
+        let l = piles.partition_point(|p| follows(&seq[p[p.len() - 1]], x));
+        let range = match l.checked_sub(1) {
+            Some(prev) => {
+                let pile = &piles[prev];
+                pile.partition_point(|&t| !follows(&seq[t], x))..pile.len()
+            }
+            None => 0..0,
+        };
+        if l == piles.len() {
+            piles.push(vec![]);
+        }
+        piles[l].push(i);
+        level.push(l);
+        predecessors.push(range);
+    }
+    Piles {
+        piles,
+        level,
+        predecessors,
+    }
+}
+
+/// The indices of a longest subsequence of `seq` that is increasing under
+///  `cmp`. When several are longest, the one returned ends at the latest
+///  possible position, and each earlier index is the latest that can
+///  precede the next, so `[1, 3, 2]` gives `[0, 2]`. An empty `seq` gives an
+///  empty subsequence.
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+/// * `cmp`: The order the subsequence must increase in
+pub fn longest_subsequence_indices_by<T, F>(seq: &[T], strictness: Strictness, cmp: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let Piles {
+        piles,
+        level,
+        predecessors,
+    } = deal(seq, strictness, cmp);
+    let mut indices = Vec::with_capacity(piles.len());
+    let mut next = piles.last().and_then(|p| p.last()).copied();
+    while let Some(i) = next {
+        indices.push(i);
+        next = level[i]
+            .checked_sub(1)
+            .map(|prev| piles[prev][predecessors[i].end - 1]);
+    }
+    indices.reverse();
+    indices
+}
+
+/// The number of distinct index sets forming a longest subsequence of
+///  `seq` that is increasing under `cmp`, in O(n log n) without listing
+///  them. An empty `seq` has one, the empty subsequence.
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+/// * `cmp`: The order the subsequences must increase in
+pub fn count_longest_by<T, F>(seq: &[T], strictness: Strictness, cmp: F) -> BigUint
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let Piles {
+        piles,
+        level,
+        predecessors,
+    } = deal(seq, strictness, cmp);
+    // `cumulative[l][k]` is the number of length `l + 1` subsequences ending
+    //  at any of the first `k` elements of pile `l`
+    let mut cumulative: Vec<Vec<BigUint>> = vec![vec![BigUint::zero()]; piles.len()];
+    for (i, &l) in level.iter().enumerate() {
+        let ways = match l.checked_sub(1) {
+            Some(prev) => {
+                let range = &predecessors[i];
+                &cumulative[prev][range.end] - &cumulative[prev][range.start]
+            }
+            None => BigUint::one(),
+        };
+        let total = cumulative[l].last().unwrap() + ways;
+        cumulative[l].push(total);
+    }
+    cumulative
+        .last()
+        .map(|c| c.last().unwrap().clone())
+        .unwrap_or_else(BigUint::one)
+}
+
+/// The indices of every longest subsequence of `seq` that is increasing
+///  under `cmp`, ordered by their last index and then from the back. There
+///  can be exponentially many, so `count_longest_by` first.
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+/// * `cmp`: The order the subsequences must increase in
+pub fn all_longest_indices_by<T, F>(seq: &[T], strictness: Strictness, cmp: F) -> Vec<Vec<usize>>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let Piles {
+        piles,
+        level,
+        predecessors,
+    } = deal(seq, strictness, cmp);
+    let top = match piles.last() {
+        Some(top) => top,
+        None => return vec![vec![]],
+    };
+
+    // Depth-first from each element of the top pile back to the first pile,
+    //  with `stack` holding the partial subsequence in reverse
+    let mut out = vec![];
+    let mut stack: Vec<usize> = vec![];
+    let mut pending: Vec<(usize, usize)> = top.iter().rev().map(|&i| (i, 0)).collect();
+    while let Some((i, depth)) = pending.pop() {
+        stack.truncate(depth);
+        stack.push(i);
+        match level[i].checked_sub(1) {
+            Some(prev) => pending.extend(
+                piles[prev][predecessors[i].clone()]
+                    .iter()
+                    .rev()
+                    .map(|&j| (j, depth + 1)),
+            ),
+            None => out.push(stack.iter().rev().copied().collect()),
+        }
+    }
+    out
+}
+
+/// A longest subsequence of `seq` that is increasing under `cmp`, e.g.
+///  `f64::total_cmp` for floats
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+/// * `cmp`: The order the subsequence must increase in
+pub fn longest_subsequence_by<T, F>(seq: &[T], strictness: Strictness, cmp: F) -> Subsequence<T>
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let indices = longest_subsequence_indices_by(seq, strictness, cmp);
+    let values = indices.iter().map(|&i| seq[i].clone()).collect();
+    Subsequence { indices, values }
+}
+
+/// A longest increasing subsequence of `seq` under its natural order
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+pub fn longest_increasing<T: Ord + Clone>(seq: &[T], strictness: Strictness) -> Subsequence<T> {
+    longest_subsequence_by(seq, strictness, T::cmp)
+}
+
+/// A longest decreasing subsequence of `seq` under its natural order
+///
+/// Arguments:
+/// * `seq`: The sequence to search
+/// * `strictness`: Whether equal neighbours are allowed
+pub fn longest_decreasing<T: Ord + Clone>(seq: &[T], strictness: Strictness) -> Subsequence<T> {
+    longest_subsequence_by(seq, strictness, |a, b| b.cmp(a))
+}
+
+/// The elements of a longest strictly increasing subsequence of `seq`
+pub fn longest_increasing_subsequence<T: Ord + Clone>(seq: &[T]) -> Vec<T> {
+    longest_increasing(seq, Strictness::Strict).values
+}
+
+/// The elements of a longest strictly decreasing subsequence of `seq`
+pub fn longest_decreasing_subsequence<T: Ord + Clone>(seq: &[T]) -> Vec<T> {
+    longest_decreasing(seq, Strictness::Strict).values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::testing::XorShift;
+
+    /// Every longest increasing index set, by trying all subsets
+    fn brute_force(seq: &[u8], strictness: Strictness) -> Vec<Vec<usize>> {
+        let mut best: Vec<Vec<usize>> = vec![];
+        for mask in 0u32..1 << seq.len() {
+            let indices: Vec<usize> = (0..seq.len()).filter(|&i| mask >> i & 1 == 1).collect();
+            let increasing = indices.windows(2).all(|w| match strictness {
+                Strictness::Strict => seq[w[0]] < seq[w[1]],
+                Strictness::NonStrict => seq[w[0]] <= seq[w[1]],
+            });
+            if !increasing || best.first().is_some_and(|b| b.len() > indices.len()) {
+                continue;
+            }
+            if best.first().is_some_and(|b| b.len() < indices.len()) {
+                best.clear();
+            }
+            best.push(indices);
+        }
+        best
+    }
+
+    #[test]
+    fn rosalind_sample() {
+        let seq = [5, 1, 4, 2, 3];
+        assert_eq!(longest_increasing_subsequence(&seq), [1, 2, 3]);
+        assert_eq!(longest_decreasing_subsequence(&seq), [5, 4, 3]);
+    }
+
+    #[test]
+    fn ties_end_at_the_latest_position() {
+        assert_eq!(
+            longest_subsequence_indices_by(&[1, 3, 2], Strictness::Strict, i32::cmp),
+            [0, 2]
+        );
+        assert!(longest_increasing::<u8>(&[], Strictness::Strict).is_empty());
+    }
+
+    #[test]
+    fn non_strict_subsequences_may_repeat() {
+        let seq = [2, 2, 1, 2];
+        assert_eq!(longest_increasing(&seq, Strictness::Strict).indices, [2, 3]);
+        assert_eq!(
+            longest_increasing(&seq, Strictness::NonStrict).indices,
+            [0, 1, 3]
+        );
+        assert_eq!(
+            count_longest_by(&seq, Strictness::NonStrict, i32::cmp),
+            BigUint::one()
+        );
+        assert_eq!(
+            count_longest_by(&seq, Strictness::Strict, i32::cmp),
+            BigUint::one()
+        );
+    }
+
+    #[test]
+    fn floats_order_by_total_cmp() {
+        let seq = [0.5, f64::NAN, -1.0, 2.0, f64::NAN, 1.5];
+        let lis = longest_subsequence_by(&seq, Strictness::Strict, f64::total_cmp);
+        // NaN sorts above every number, so it can end a subsequence
+        assert_eq!(lis.indices, [2, 3, 4]);
+        assert_eq!(
+            count_longest_by(&seq, Strictness::Strict, f64::total_cmp),
+            BigUint::from(2u8)
+        );
+        // Without strictness the two NaNs may follow each other
+        assert!(
+            all_longest_indices_by(&seq, Strictness::NonStrict, f64::total_cmp)
+                .contains(&vec![0, 1, 4])
+        );
+        assert_eq!(
+            count_longest_by(&seq, Strictness::NonStrict, f64::total_cmp),
+            BigUint::from(3u8)
+        );
+    }
+
+    #[test]
+    fn empty_sequence_has_one_empty_subsequence() {
+        assert_eq!(
+            count_longest_by::<u8, _>(&[], Strictness::Strict, u8::cmp),
+            BigUint::one()
+        );
+        assert_eq!(
+            all_longest_indices_by::<u8, _>(&[], Strictness::Strict, u8::cmp),
+            [Vec::<usize>::new()]
+        );
+    }
+
+    #[test]
+    fn random_sequences_agree_with_brute_force() {
+        let mut rng = XorShift::new(0x9e3779b97f4a7c15);
+        for round in 0..400 {
+            let len = round % 13;
+            let seq: Vec<u8> = (0..len).map(|_| rng.below(6) as u8).collect();
+            for strictness in [Strictness::Strict, Strictness::NonStrict] {
+                let mut expected = brute_force(&seq, strictness);
+                let mut all = all_longest_indices_by(&seq, strictness, u8::cmp);
+                assert_eq!(
+                    count_longest_by(&seq, strictness, u8::cmp),
+                    BigUint::from(expected.len())
+                );
+                let one = longest_subsequence_indices_by(&seq, strictness, u8::cmp);
+                assert!(expected.contains(&one), "{:?} {:?}", seq, one);
+                all.sort();
+                expected.sort();
+                assert_eq!(all, expected, "{:?} {:?}", seq, strictness);
+            }
+        }
+    }
+}
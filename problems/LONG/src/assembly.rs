@@ -0,0 +1,263 @@
+//! Greedy shortest-superstring assembly: repeatedly join the two reads with
+//!  the longest remaining overlap, as long as that keeps every contig a
+//!  simple path
+
+use std::error;
+use std::fmt;
+
+use crate::overlap::{self, Containment, Overlap};
+
+/// Why a set of reads could not be assembled into a single superstring
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblyError {
+    /// There were no reads at all
+    NoReads,
+    /// No chain of long enough overlaps joins every read
+    Disconnected { contigs: usize, min_overlap: usize },
+    /// A de Bruijn graph was asked for k-mers of length 0
+    EmptyKmer,
+    /// Circular assembly was asked for k-mers too short to overlap
+    ShortKmer { k: usize },
+    /// The k-mers of a de Bruijn graph do not all have the same length
+    MixedKmerLengths { expected: usize, found: usize },
+    /// The de Bruijn graph of the reads is not a single cycle
+    NotCyclic { k: usize },
+    /// No k-mer length splits the reads and their reverse complements into
+    ///  one cycle per strand
+    NoCyclicKmerLength,
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssemblyError::NoReads => write!(f, "there are no reads to assemble"),
+            AssemblyError::Disconnected {
+                contigs,
+                min_overlap,
+            } => write!(
+                f,
+                "the reads assemble into {} separate contigs; no chain of overlaps of at least {} bases joins them all",
+                contigs, min_overlap
+            ),
+            AssemblyError::EmptyKmer => write!(f, "k-mers must be at least one base long"),
+            AssemblyError::ShortKmer { k } => write!(
+                f,
+                "circular assembly needs reads of at least 2 bases, but these are {} long",
+                k
+            ),
+            AssemblyError::MixedKmerLengths { expected, found } => write!(
+                f,
+                "every k-mer must have the same length, but found {} after {}",
+                found, expected
+            ),
+            AssemblyError::NotCyclic { k } => write!(
+                f,
+                "the de Bruijn graph of the {}-mers is not a single cycle",
+                k
+            ),
+            AssemblyError::NoCyclicKmerLength => write!(
+                f,
+                "no k-mer length gives one cycle per strand of a circular genome"
+            ),
+        }
+    }
+}
+
+impl error::Error for AssemblyError {}
+
+/// Where one read lies within a contig
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+    /// The read's position in the input
+    pub read: usize,
+    /// The 0-based position of the read's first base in the contig
+    pub offset: usize,
+    /// The bases shared with the previous read on the contig's path, or 0
+    ///  for the first read and for reads contained in another
+    pub overlap: usize,
+}
+
+/// An assembled sequence and the reads it was built from, in order of
+///  their offsets
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contig {
+    pub seq: Vec<u8>,
+    pub layout: Vec<Placement>,
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Assemble reads into as few contigs as greedy joining allows, taking
+///  overlaps longest first and skipping any that would branch a contig or
+///  close it into a cycle. Reads inside other reads are placed within
+///  them rather than joined.
+///
+/// Arguments:
+/// * `reads`: The read sequences
+/// * `min_overlap`: The shortest overlap allowed to join two reads
+pub fn assemble_contigs(reads: &[&[u8]], min_overlap: usize) -> Vec<Contig> {
+    let (kept, containments) = overlap::find_containments(reads);
+    let mut overlaps = overlap::find_overlaps(reads, &kept, min_overlap);
+    overlaps.sort_by(|a, b| b.len.cmp(&a.len).then((a.from, a.to).cmp(&(b.from, b.to))));
+
+    let n = reads.len();
+    let mut next: Vec<Option<Overlap>> = vec![None; n];
+    let mut has_prev = vec![false; n];
+    let mut parent: Vec<usize> = (0..n).collect();
+    for o in overlaps {
+        if next[o.from].is_some() || has_prev[o.to] {
+            continue;
+        }
+        let (a, b) = (find(&mut parent, o.from), find(&mut parent, o.to));
+        if a == b {
+            continue;
+        }
+        parent[a] = b;
+        next[o.from] = Some(o);
+        has_prev[o.to] = true;
+    }
+
+    let mut contigs: Vec<Contig> = vec![];
+    let mut contig_of = vec![(0, 0); n];
+    for &start in kept.iter().filter(|&&i| !has_prev[i]) {
+        let mut seq = reads[start].to_vec();
+        let mut layout = vec![Placement {
+            read: start,
+            offset: 0,
+            overlap: 0,
+        }];
+        contig_of[start] = (contigs.len(), 0);
+        let mut at = start;
+        while let Some(o) = next[at] {
+            let offset = seq.len() - o.len;
+            seq.extend_from_slice(&reads[o.to][o.len..]);
+            layout.push(Placement {
+                read: o.to,
+                offset,
+                overlap: o.len,
+            });
+            contig_of[o.to] = (contigs.len(), offset);
+            at = o.to;
+        }
+        contigs.push(Contig { seq, layout });
+    }
+
+    for Containment {
+        read,
+        container,
+        offset,
+    } in containments
+    {
+        let (contig, base) = contig_of[container];
+        contigs[contig].layout.push(Placement {
+            read,
+            offset: base + offset,
+            overlap: 0,
+        });
+    }
+    for contig in contigs.iter_mut() {
+        contig.layout.sort_by_key(|p| (p.offset, p.read));
+    }
+    contigs
+}
+
+/// Assemble reads into a single superstring, or report why they do not
+///  join into one
+///
+/// Arguments:
+/// * `reads`: The read sequences
+/// * `min_overlap`: The shortest overlap allowed to join two reads
+pub fn assemble_one(reads: &[&[u8]], min_overlap: usize) -> Result<Contig, AssemblyError> {
+    if reads.is_empty() {
+        return Err(AssemblyError::NoReads);
+    }
+    let mut contigs = assemble_contigs(reads, min_overlap);
+    if contigs.len() > 1 {
+        return Err(AssemblyError::Disconnected {
+            contigs: contigs.len(),
+            min_overlap,
+        });
+    }
+    Ok(contigs.remove(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(read: usize, offset: usize, overlap: usize) -> Placement {
+        Placement {
+            read,
+            offset,
+            overlap,
+        }
+    }
+
+    #[test]
+    fn rosalind_sample() {
+        let reads: [&[u8]; 4] = [b"ATTAGACCTG", b"CCTGCCGGAA", b"AGACCTGCCG", b"GCCGGAATAC"];
+        let contig = assemble_one(&reads, 5).unwrap();
+        assert_eq!(contig.seq, b"ATTAGACCTGCCGGAATAC");
+        assert_eq!(
+            contig.layout,
+            [
+                placement(0, 0, 0),
+                placement(2, 3, 7),
+                placement(1, 6, 7),
+                placement(3, 9, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn contained_reads_are_placed_inside_their_container() {
+        let reads: [&[u8]; 3] = [b"ACGTACGG", b"GTAC", b"ACGGTTCA"];
+        let contig = assemble_one(&reads, 3).unwrap();
+        assert_eq!(contig.seq, b"ACGTACGGTTCA");
+        assert_eq!(
+            contig.layout,
+            [placement(0, 0, 0), placement(1, 2, 0), placement(2, 4, 4)]
+        );
+    }
+
+    #[test]
+    fn identical_reads_collapse_onto_the_first() {
+        let reads: [&[u8]; 3] = [b"GATTACA", b"GATTACA", b"ACAGGT"];
+        let contig = assemble_one(&reads, 3).unwrap();
+        assert_eq!(contig.seq, b"GATTACAGGT");
+        assert_eq!(
+            contig.layout,
+            [placement(0, 0, 0), placement(1, 0, 0), placement(2, 4, 3)]
+        );
+    }
+
+    #[test]
+    fn reads_without_overlaps_stay_apart() {
+        let reads: [&[u8]; 3] = [b"AAAACCCC", b"GGGGTTTT", b"CCCCGG"];
+        let contigs = assemble_contigs(&reads, 3);
+        let seqs: Vec<&[u8]> = contigs.iter().map(|c| c.seq.as_slice()).collect();
+        assert_eq!(seqs, [b"AAAACCCCGG".as_slice(), b"GGGGTTTT"]);
+        assert_eq!(
+            assemble_one(&reads, 3),
+            Err(AssemblyError::Disconnected {
+                contigs: 2,
+                min_overlap: 3
+            })
+        );
+        assert_eq!(assemble_one(&[], 3), Err(AssemblyError::NoReads));
+    }
+
+    #[test]
+    fn joins_never_close_a_cycle() {
+        let reads: [&[u8]; 2] = [b"ACGTAC", b"TACACG"];
+        let contigs = assemble_contigs(&reads, 3);
+        assert_eq!(contigs.len(), 1);
+        assert_eq!(contigs[0].layout.len(), 2);
+    }
+}
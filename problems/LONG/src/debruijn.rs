@@ -0,0 +1,485 @@
+//! De Bruijn graphs, whose nodes are the (k-1)-mers of a set of reads and
+//!  whose edges are their distinct k-mers, for assembly by unitig
+//!  compaction or Eulerian walks (Rosalind DBRU, PCOV and GASM)
+
+use std::collections::{BTreeSet, HashMap};
+
+use bio::alphabets::dna;
+use rosalind_core::fasta;
+
+use crate::assembly::AssemblyError;
+
+/// The de Bruijn graph of a set of k-mers
+#[derive(Clone, Debug)]
+pub struct DeBruijnGraph {
+    k: usize,
+    nodes: Vec<Vec<u8>>,
+    edges: Vec<(usize, usize)>,
+    successors: Vec<Vec<usize>>,
+    in_degree: Vec<usize>,
+}
+
+impl DeBruijnGraph {
+    /// The graph of the distinct k-mers given, each joining its prefix
+    ///  (k-1)-mer to its suffix (k-1)-mer
+    ///
+    /// Arguments:
+    /// * `kmers`: The k-mers, all of the same length k >= 1
+    /// * `reverse_complements`: Whether to add each k-mer's reverse
+    ///   complement too
+    pub fn from_kmers<I, S>(
+        kmers: I,
+        reverse_complements: bool,
+    ) -> Result<DeBruijnGraph, AssemblyError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let mut distinct: BTreeSet<Vec<u8>> = BTreeSet::new();
+        let mut k = None;
+        for kmer in kmers {
+            let kmer = kmer.as_ref();
+            match k {
+                None if kmer.is_empty() => return Err(AssemblyError::EmptyKmer),
+                None => k = Some(kmer.len()),
+                Some(k) if k != kmer.len() => {
+                    return Err(AssemblyError::MixedKmerLengths {
+                        expected: k,
+                        found: kmer.len(),
+                    })
+                }
+                Some(_) => {}
+            }
+            if reverse_complements {
+                distinct.insert(dna::revcomp(kmer));
+            }
+            distinct.insert(kmer.to_vec());
+        }
+
+        let mut graph = DeBruijnGraph {
+            k: k.unwrap_or(1),
+            nodes: vec![],
+            edges: vec![],
+            successors: vec![],
+            in_degree: vec![],
+        };
+        let mut index: HashMap<Vec<u8>, usize> = HashMap::new();
+        for kmer in distinct {
+            let mut node = |seq: &[u8]| {
+                *index.entry(seq.to_vec()).or_insert_with(|| {
+                    graph.nodes.push(seq.to_vec());
+                    graph.successors.push(vec![]);
+                    graph.in_degree.push(0);
+                    graph.nodes.len() - 1
+                })
+            };
+            let from = node(&kmer[..kmer.len() - 1]);
+            let to = node(&kmer[1..]);
+            graph.edges.push((from, to));
+            graph.successors[from].push(to);
+            graph.in_degree[to] += 1;
+        }
+        Ok(graph)
+    }
+
+    /// The graph of every k-mer in the reads. Reads shorter than `k`
+    ///  contribute nothing.
+    ///
+    /// Arguments:
+    /// * `records`: The reads
+    /// * `k`: The k-mer length, at least 1
+    /// * `reverse_complements`: Whether to add each k-mer's reverse
+    ///   complement too
+    pub fn from_reads(
+        records: &[fasta::Record],
+        k: usize,
+        reverse_complements: bool,
+    ) -> Result<DeBruijnGraph, AssemblyError> {
+        if k == 0 {
+            return Err(AssemblyError::EmptyKmer);
+        }
+        let mut graph = DeBruijnGraph::from_kmers(
+            records.iter().flat_map(|r| r.seq().windows(k)),
+            reverse_complements,
+        )?;
+        graph.k = k;
+        Ok(graph)
+    }
+
+    /// The length of the k-mers on the edges
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The (k-1)-mers, indexed as in `edges`
+    pub fn nodes(&self) -> &[Vec<u8>] {
+        &self.nodes
+    }
+
+    /// One `(from, to)` pair of node indices per distinct k-mer, in the
+    ///  k-mers' sort order
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// The edges as pairs of (k-1)-mers, as Rosalind DBRU lists them
+    pub fn adjacency_list(&self) -> Vec<(&[u8], &[u8])> {
+        self.edges
+            .iter()
+            .map(|&(from, to)| (self.nodes[from].as_slice(), self.nodes[to].as_slice()))
+            .collect()
+    }
+
+    fn is_one_in_one_out(&self, node: usize) -> bool {
+        self.in_degree[node] == 1 && self.successors[node].len() == 1
+    }
+
+    /// The sequence a path of nodes spells: the first node, then the last
+    ///  base of each node after it
+    ///
+    /// Arguments:
+    /// * `path`: Node indices, each joined to the next by an edge
+    pub fn spell(&self, path: &[usize]) -> Vec<u8> {
+        let mut seq = path
+            .first()
+            .map(|&n| self.nodes[n].clone())
+            .unwrap_or_default();
+        seq.extend(path.iter().skip(1).filter_map(|&n| self.nodes[n].last()));
+        seq
+    }
+
+    /// The circular sequence a cycle of nodes spells, as the first base of
+    ///  each node in turn. This needs k >= 2, since 1-mers leave the nodes
+    ///  empty.
+    ///
+    /// Arguments:
+    /// * `cycle`: Node indices, each joined to the next and the last to the
+    ///   first by an edge
+    pub fn spell_cycle(&self, cycle: &[usize]) -> Vec<u8> {
+        cycle
+            .iter()
+            .filter_map(|&n| self.nodes[n].first().copied())
+            .collect()
+    }
+
+    /// The maximal non-branching paths, as node indices, with isolated
+    ///  cycles listed from their lowest node and repeating it at the end
+    pub fn unitig_paths(&self) -> Vec<Vec<usize>> {
+        let mut used = vec![false; self.nodes.len()];
+        let mut paths = vec![];
+        for v in 0..self.nodes.len() {
+            if self.is_one_in_one_out(v) {
+                continue;
+            }
+            for &w in self.successors[v].iter() {
+                let mut path = vec![v, w];
+                used[v] = true;
+                let mut at = w;
+                while self.is_one_in_one_out(at) {
+                    used[at] = true;
+                    at = self.successors[at][0];
+                    path.push(at);
+                }
+                paths.push(path);
+            }
+        }
+        for v in 0..self.nodes.len() {
+            if used[v] || !self.is_one_in_one_out(v) {
+                continue;
+            }
+            let mut cycle = vec![v];
+            let mut at = v;
+            loop {
+                used[at] = true;
+                at = self.successors[at][0];
+                cycle.push(at);
+                if at == v {
+                    break;
+                }
+            }
+            paths.push(cycle);
+        }
+        paths
+    }
+
+    /// The contigs spelled by the maximal non-branching paths
+    pub fn unitigs(&self) -> Vec<Vec<u8>> {
+        self.unitig_paths().iter().map(|p| self.spell(p)).collect()
+    }
+
+    /// A walk using every edge exactly once, by Hierholzer's algorithm. It
+    ///  is a cycle, starting and ending at the same node, when every node is
+    ///  balanced. Returns `None` if the graph has no edges or no such walk.
+    pub fn eulerian_path(&self) -> Option<Vec<usize>> {
+        let mut start = None;
+        let (mut sources, mut sinks) = (0, 0);
+        for v in 0..self.nodes.len() {
+            let out = self.successors[v].len();
+            let inn = self.in_degree[v];
+            if out == inn + 1 {
+                sources += 1;
+                start = Some(v);
+            } else if inn == out + 1 {
+                sinks += 1;
+            } else if inn != out {
+                return None;
+            }
+        }
+        if sources > 1 || sinks != sources {
+            return None;
+        }
+        let start =
+            start.or_else(|| (0..self.nodes.len()).find(|&v| !self.successors[v].is_empty()))?;
+
+        let mut next_edge = vec![0usize; self.nodes.len()];
+        let mut stack = vec![start];
+        let mut path = vec![];
+        while let Some(&v) = stack.last() {
+            match self.successors[v].get(next_edge[v]) {
+                Some(&w) => {
+                    next_edge[v] += 1;
+                    stack.push(w);
+                }
+                None => path.push(stack.pop().unwrap()),
+            }
+        }
+        path.reverse();
+        // A shorter walk means some edges lie in another component
+        if path.len() == self.edges.len() + 1 {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// The graph split into its disjoint simple cycles, each listed once
+    ///  from its lowest node, or `None` unless every node has exactly one
+    ///  edge in and one edge out
+    pub fn cycles(&self) -> Option<Vec<Vec<usize>>> {
+        if !(0..self.nodes.len()).all(|v| self.is_one_in_one_out(v)) {
+            return None;
+        }
+        let mut seen = vec![false; self.nodes.len()];
+        let mut cycles = vec![];
+        for v in 0..self.nodes.len() {
+            if seen[v] {
+                continue;
+            }
+            let mut cycle = vec![];
+            let mut at = v;
+            while !seen[at] {
+                seen[at] = true;
+                cycle.push(at);
+                at = self.successors[at][0];
+            }
+            cycles.push(cycle);
+        }
+        Some(cycles)
+    }
+}
+
+/// The circular genome that reads of equal length `k` cover perfectly, read
+///  off the single cycle of their de Bruijn graph (Rosalind PCOV). The
+///  reads must be at least 2 bases long.
+///
+/// Arguments:
+/// * `records`: The reads, which serve as the graph's k-mers
+pub fn assemble_circular(records: &[fasta::Record]) -> Result<Vec<u8>, AssemblyError> {
+    if records.is_empty() {
+        return Err(AssemblyError::NoReads);
+    }
+    let graph = DeBruijnGraph::from_kmers(records.iter().map(|r| r.seq()), false)?;
+    if graph.k() < 2 {
+        return Err(AssemblyError::ShortKmer { k: graph.k() });
+    }
+    match graph.cycles() {
+        Some(cycles) if cycles.len() == 1 => Ok(graph.spell_cycle(&cycles[0])),
+        _ => Err(AssemblyError::NotCyclic { k: graph.k() }),
+    }
+}
+
+/// The shortest circular genome whose strands contain every read, taking
+///  the largest k for which the k-mers of the reads and their reverse
+///  complements form exactly two cycles, one per strand (Rosalind GASM).
+///  k never exceeds the shortest read, so every read contributes k-mers.
+///
+/// Arguments:
+/// * `records`: The reads, from either strand
+pub fn assemble_circular_double_stranded(
+    records: &[fasta::Record],
+) -> Result<Vec<u8>, AssemblyError> {
+    let shortest = records
+        .iter()
+        .map(|r| r.seq().len())
+        .min()
+        .ok_or(AssemblyError::NoReads)?;
+    for k in (2..=shortest).rev() {
+        let graph = DeBruijnGraph::from_reads(records, k, true)?;
+        if let Some(cycles) = graph.cycles() {
+            if cycles.len() == 2 {
+                return Ok(graph.spell_cycle(&cycles[0]));
+            }
+        }
+    }
+    Err(AssemblyError::NoCyclicKmerLength)
+}
+
+/// Whether `a` and `b` are the same circular sequence, e.g. a genome
+///  assembled from a different starting point
+///
+/// Arguments:
+/// * `a`: One sequence, read from any starting point
+/// * `b`: The other sequence
+pub fn is_rotation(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && (b.is_empty() || [a, a].concat().windows(b.len()).any(|w| w == b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(reads: &[&str]) -> Vec<fasta::Record> {
+        reads
+            .iter()
+            .enumerate()
+            .map(|(i, seq)| fasta::Record::with_attrs(&i.to_string(), None, seq.as_bytes()))
+            .collect()
+    }
+
+    fn sorted(mut seqs: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        seqs.sort();
+        seqs
+    }
+
+    #[test]
+    fn dbru_sample() {
+        let reads = records(&["TGAT", "CATG", "TCAT", "ATGC", "CATC", "CATC"]);
+        let graph = DeBruijnGraph::from_reads(&reads, 4, true).unwrap();
+        let edges: Vec<(&[u8], &[u8])> = graph.adjacency_list();
+        let expected: Vec<(&[u8], &[u8])> = vec![
+            (b"ATC", b"TCA"),
+            (b"ATG", b"TGA"),
+            (b"ATG", b"TGC"),
+            (b"CAT", b"ATC"),
+            (b"CAT", b"ATG"),
+            (b"GAT", b"ATG"),
+            (b"GCA", b"CAT"),
+            (b"TCA", b"CAT"),
+            (b"TGA", b"GAT"),
+        ];
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn kmers_must_share_a_length() {
+        assert_eq!(
+            DeBruijnGraph::from_kmers(["ACG", "CG"], false).unwrap_err(),
+            AssemblyError::MixedKmerLengths {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            DeBruijnGraph::from_kmers([""], false).unwrap_err(),
+            AssemblyError::EmptyKmer
+        );
+        assert_eq!(
+            DeBruijnGraph::from_reads(&records(&["ACGT"]), 0, false).unwrap_err(),
+            AssemblyError::EmptyKmer
+        );
+    }
+
+    #[test]
+    fn unitigs_stop_at_branches_and_close_cycles() {
+        let branching = DeBruijnGraph::from_kmers(["ATG", "TGC", "TGA"], false).unwrap();
+        assert_eq!(
+            sorted(branching.unitigs()),
+            [b"ATG".to_vec(), b"TGA".to_vec(), b"TGC".to_vec()]
+        );
+        let cycle = DeBruijnGraph::from_kmers(["AC", "CA"], false).unwrap();
+        assert_eq!(cycle.unitig_paths(), [vec![0, 1, 0]]);
+        assert_eq!(cycle.unitigs(), [b"ACA".to_vec()]);
+        let line = DeBruijnGraph::from_reads(&records(&["ACGGT"]), 3, false).unwrap();
+        assert_eq!(line.unitigs(), [b"ACGGT".to_vec()]);
+    }
+
+    #[test]
+    fn eulerian_paths_use_every_edge_once() {
+        let line = DeBruijnGraph::from_reads(&records(&["ATGCA"]), 3, false).unwrap();
+        assert_eq!(line.spell(&line.eulerian_path().unwrap()), b"ATGCA");
+
+        let cycle = DeBruijnGraph::from_reads(&records(&["ACGTAC"]), 3, false).unwrap();
+        let path = cycle.eulerian_path().unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.first(), path.last());
+
+        let repeat = DeBruijnGraph::from_reads(&records(&["AGAGT"]), 2, false).unwrap();
+        let walk = repeat.spell(&repeat.eulerian_path().unwrap());
+        assert_eq!(walk.len(), repeat.edges().len() + 1);
+
+        let apart = DeBruijnGraph::from_kmers(["AAC", "GGT"], false).unwrap();
+        assert_eq!(apart.eulerian_path(), None);
+        let split_cycles = DeBruijnGraph::from_kmers(["AC", "CA", "GT", "TG"], false).unwrap();
+        assert_eq!(split_cycles.eulerian_path(), None);
+        let empty = DeBruijnGraph::from_kmers(Vec::<&[u8]>::new(), false).unwrap();
+        assert_eq!(empty.eulerian_path(), None);
+    }
+
+    #[test]
+    fn pcov_sample() {
+        let reads = records(&[
+            "ATTAC", "TACAG", "GATTA", "ACAGA", "CAGAT", "TTACA", "AGATT",
+        ]);
+        let genome = assemble_circular(&reads).unwrap();
+        assert!(is_rotation(b"GATTACA", &genome), "{:?}", genome);
+    }
+
+    #[test]
+    fn rotations_are_the_same_circular_sequence() {
+        assert!(is_rotation(b"GATTACA", b"TACAGAT"));
+        assert!(is_rotation(b"", b""));
+        assert!(!is_rotation(b"GATTACA", b"GATTAC"));
+        assert!(!is_rotation(b"GATTACA", b"ACATTAG"));
+    }
+
+    #[test]
+    fn circular_assembly_needs_one_cycle_of_overlapping_kmers() {
+        assert_eq!(assemble_circular(&[]), Err(AssemblyError::NoReads));
+        assert_eq!(
+            assemble_circular(&records(&["A", "C"])),
+            Err(AssemblyError::ShortKmer { k: 1 })
+        );
+        assert_eq!(
+            assemble_circular(&records(&["ACG", "CGT"])),
+            Err(AssemblyError::NotCyclic { k: 3 })
+        );
+    }
+
+    #[test]
+    fn gasm_sample() {
+        let reads = records(&["AATCT", "TGTAA", "GATTA", "ACAGA"]);
+        let genome = assemble_circular_double_stranded(&reads).unwrap();
+        let reverse = dna::revcomp(&genome);
+        assert!(
+            is_rotation(b"GATTACA", &genome) || is_rotation(b"GATTACA", &reverse),
+            "{:?}",
+            genome
+        );
+    }
+
+    #[test]
+    fn double_stranded_k_never_exceeds_the_shortest_read() {
+        // At k = 4 the stray read would contribute nothing and the rest
+        //  close up; capping k keeps it in the graph, where it breaks the
+        //  cycles
+        let reads = records(&["ATTAC", "TACAG", "CAGAT", "AGATT", "GGG"]);
+        assert_eq!(
+            assemble_circular_double_stranded(&reads),
+            Err(AssemblyError::NoCyclicKmerLength)
+        );
+        assert_eq!(
+            assemble_circular_double_stranded(&[]),
+            Err(AssemblyError::NoReads)
+        );
+    }
+}
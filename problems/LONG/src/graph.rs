@@ -0,0 +1,191 @@
+//! The overlap graph of a set of reads, whose edges join each read to every
+//!  read its suffix overlaps, for Rosalind GRPH output or GFA export, and
+//!  an order-free comparison of edge listings for checking answers
+
+use std::fmt::Write;
+use std::str;
+
+use rosalind_core::fasta;
+
+use crate::overlap::{self, Overlap};
+
+/// Every read, and every suffix-prefix overlap of at least `min_overlap`
+///  bases between two different reads
+#[derive(Clone, Debug)]
+pub struct OverlapGraph<'a> {
+    records: &'a [fasta::Record],
+    min_overlap: usize,
+    edges: Vec<Overlap>,
+}
+
+impl<'a> OverlapGraph<'a> {
+    /// Arguments:
+    /// * `records`: The reads, which become the graph's nodes
+    /// * `min_overlap`: The shortest overlap that makes an edge, at least 1
+    pub fn new(records: &'a [fasta::Record], min_overlap: usize) -> OverlapGraph<'a> {
+        let reads: Vec<&[u8]> = records.iter().map(|r| r.seq()).collect();
+        let all: Vec<usize> = (0..reads.len()).collect();
+        let mut edges = overlap::find_overlaps(&reads, &all, min_overlap);
+        edges.sort();
+        OverlapGraph {
+            records,
+            min_overlap: min_overlap.max(1),
+            edges,
+        }
+    }
+
+    /// The reads, indexed as in each edge
+    pub fn records(&self) -> &'a [fasta::Record] {
+        self.records
+    }
+
+    /// The shortest overlap the graph has an edge for
+    pub fn min_overlap(&self) -> usize {
+        self.min_overlap
+    }
+
+    /// Every edge, ordered by source read, then target read, then length
+    pub fn edges(&self) -> &[Overlap] {
+        &self.edges
+    }
+
+    /// The edges as read ids and overlap lengths
+    pub fn labelled_edges(&self) -> impl Iterator<Item = (&'a str, &'a str, usize)> + '_ {
+        let records = self.records;
+        self.edges
+            .iter()
+            .map(move |o| (records[o.from].id(), records[o.to].id(), o.len))
+    }
+
+    /// One `from to` line of read ids per pair whose overlap is exactly the
+    ///  minimum length, as in Rosalind GRPH
+    pub fn to_grph(&self) -> String {
+        self.labelled_edges()
+            .filter(|&(_, _, len)| len == self.min_overlap)
+            .map(|(from, to, _)| format!("{} {}", from, to))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// The graph in GFA 1.0, with a segment per read and a link per pair of
+    ///  overlapping reads, all on the forward strand. A pair overlapping at
+    ///  several lengths is linked once, by its longest overlap.
+    pub fn to_gfa(&self) -> String {
+        let mut out = String::from("H\tVN:Z:1.0\n");
+        for r in self.records {
+            let seq = str::from_utf8(r.seq()).unwrap();
+            writeln!(out, "S\t{}\t{}\tLN:i:{}", r.id(), seq, seq.len()).unwrap();
+        }
+        // Edges are sorted by length within each pair, so the last is longest
+        for pair in self.edges.chunk_by(|a, b| (a.from, a.to) == (b.from, b.to)) {
+            let o = pair[pair.len() - 1];
+            let (from, to) = (self.records[o.from].id(), self.records[o.to].id());
+            writeln!(out, "L\t{}\t+\t{}\t+\t{}M", from, to, o.len).unwrap();
+        }
+        out
+    }
+}
+
+/// Whether two edge listings, one edge per line, name the same edges in
+///  any order, ignoring blank lines and differences in spacing
+///
+/// Arguments:
+/// * `expected`: One listing
+/// * `actual`: The other listing
+pub fn same_edges(expected: &str, actual: &str) -> bool {
+    let edges = |s: &str| {
+        let mut lines: Vec<String> = s
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<&str>>().join(" "))
+            .filter(|l| !l.is_empty())
+            .collect();
+        lines.sort();
+        lines
+    };
+    edges(expected) == edges(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(reads: &[(&str, &str)]) -> Vec<fasta::Record> {
+        reads
+            .iter()
+            .map(|(id, seq)| fasta::Record::with_attrs(id, None, seq.as_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn grph_lists_overlaps_of_exactly_the_minimum() {
+        let reads = records(&[
+            ("Rosalind_0498", "AAATAAA"),
+            ("Rosalind_2391", "AAATTTT"),
+            ("Rosalind_2323", "TTTTCCC"),
+            ("Rosalind_0442", "AAATCCC"),
+            ("Rosalind_5013", "GGGTGGG"),
+        ]);
+        let graph = OverlapGraph::new(&reads, 3);
+        assert_eq!(
+            graph.to_grph(),
+            "Rosalind_0498 Rosalind_2391\n\
+             Rosalind_0498 Rosalind_0442\n\
+             Rosalind_2391 Rosalind_2323"
+        );
+    }
+
+    #[test]
+    fn gfa_has_a_header_segments_and_links() {
+        let reads = records(&[("a", "ACGTAC"), ("b", "TACGGA"), ("c", "CCCCCC")]);
+        let gfa = OverlapGraph::new(&reads, 2).to_gfa();
+        let lines: Vec<Vec<&str>> = gfa.lines().map(|l| l.split('\t').collect()).collect();
+        assert_eq!(lines[0], ["H", "VN:Z:1.0"]);
+        assert_eq!(lines[1], ["S", "a", "ACGTAC", "LN:i:6"]);
+        assert_eq!(lines[3], ["S", "c", "CCCCCC", "LN:i:6"]);
+        assert_eq!(lines[4], ["L", "a", "+", "b", "+", "3M"]);
+        assert_eq!(lines.len(), 5);
+        // Every link joins two segments and its overlap really is shared
+        let seq = |name: &str| {
+            lines
+                .iter()
+                .find(|l| l[0] == "S" && l[1] == name)
+                .map(|l| l[2])
+                .unwrap()
+        };
+        for link in lines.iter().filter(|l| l[0] == "L") {
+            let len: usize = link[5].strip_suffix('M').unwrap().parse().unwrap();
+            let (from, to) = (seq(link[1]), seq(link[3]));
+            assert_eq!(from[from.len() - len..], to[..len]);
+        }
+        assert!(gfa.ends_with('\n'));
+    }
+
+    #[test]
+    fn gfa_links_each_pair_once_by_its_longest_overlap() {
+        let reads = records(&[("a", "AAATTT"), ("b", "TTTGGG")]);
+        let graph = OverlapGraph::new(&reads, 2);
+        assert_eq!(graph.edges().len(), 2);
+        let gfa = graph.to_gfa();
+        let links: Vec<&str> = gfa.lines().filter(|l| l.starts_with("L\t")).collect();
+        assert_eq!(links, ["L\ta\t+\tb\t+\t3M"]);
+        assert_eq!(graph.to_grph(), "a b");
+    }
+
+    #[test]
+    fn graphs_without_overlaps_have_no_links() {
+        let reads = records(&[("a", "AAAA"), ("b", "CCCC")]);
+        let graph = OverlapGraph::new(&reads, 2);
+        assert!(graph.edges().is_empty());
+        assert_eq!(graph.to_grph(), "");
+        assert!(!graph.to_gfa().contains("\nL\t"));
+        assert_eq!(OverlapGraph::new(&reads, 0).min_overlap(), 1);
+    }
+
+    #[test]
+    fn edge_listings_compare_in_any_order() {
+        assert!(same_edges("a b\nb c\n", " b  c\n\na b"));
+        assert!(same_edges("(AT, TG)\n(GA, AT)", "(GA, AT)\n(AT, TG)\n"));
+        assert!(!same_edges("a b\nb c", "a b"));
+        assert!(!same_edges("a b", "b a"));
+    }
+}
@@ -0,0 +1,198 @@
+pub mod assembly;
+pub mod debruijn;
+pub mod graph;
+pub mod overlap;
+pub mod report;
+
+use std::fs;
+use std::path::PathBuf;
+use std::str;
+
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Options, Problem};
+
+pub use assembly::{AssemblyError, Contig, Placement};
+pub use debruijn::DeBruijnGraph;
+pub use graph::OverlapGraph;
+pub use overlap::{Containment, Overlap};
+
+/// The minimum overlap Rosalind guarantees between adjacent reads: half
+///  the shortest read, and at least one base
+///
+/// Arguments:
+/// * `records`: The reads to assemble
+pub fn default_min_overlap(records: &[fasta::Record]) -> usize {
+    let min_len = records.iter().map(|r| r.seq().len()).min().unwrap_or(0);
+    (min_len / 2).max(1)
+}
+
+/// What LONG reports for a set of reads
+#[derive(Clone, Debug)]
+pub enum Report {
+    /// The assembled superstring
+    Superstring(fasta::Record),
+    /// One FASTA record per contig, with its reads in the description
+    Contigs(Vec<fasta::Record>),
+    /// The overlap graph, in GFA 1.0
+    Gfa(String),
+}
+
+/// What LONG produced for a set of reads
+#[derive(Clone, Debug)]
+pub struct Assembly {
+    /// What to print
+    pub report: Report,
+    /// The position of every read as TSV, if a layout was asked for
+    pub layout: Option<String>,
+}
+
+/// Genome Assembly as Shortest Superstring
+#[derive(Default)]
+pub struct Long {
+    /// The shortest overlap allowed to join two reads, or half the
+    ///  shortest read if `None`
+    pub min_overlap: Option<usize>,
+    /// Export the overlap graph as GFA instead of assembling
+    pub gfa: bool,
+    /// Print every contig as FASTA with its provenance, rather than
+    ///  requiring a single superstring
+    pub fasta: bool,
+    /// Also write the position of every read to this TSV file
+    pub layout: Option<PathBuf>,
+}
+
+impl Problem for Long {
+    type Input = Vec<fasta::Record>;
+    type Output = Assembly;
+
+    fn id(&self) -> &'static str {
+        "LONG"
+    }
+
+    /// `--min-overlap <k>` sets the shortest overlap joining two reads,
+    ///  `--gfa` prints the overlap graph instead of the superstring,
+    ///  `--fasta` prints every contig as FASTA with the reads it came from,
+    ///  and `--layout <file>` also writes where each read lies as TSV
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.min_overlap = options.take("min-overlap")?;
+        self.gfa = options.take_flag("gfa")?;
+        self.fasta = options.take_flag("fasta")?;
+        self.layout = options.take("layout")?;
+        if self.gfa && (self.fasta || self.layout.is_some()) {
+            return Err("--gfa cannot be combined with --fasta or --layout".into());
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_fasta(input.as_bytes(), Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let min_overlap = self
+            .min_overlap
+            .unwrap_or_else(|| default_min_overlap(&input));
+        if self.gfa {
+            return Ok(Assembly {
+                report: Report::Gfa(OverlapGraph::new(&input, min_overlap).to_gfa()),
+                layout: None,
+            });
+        }
+        let reads: Vec<&[u8]> = input.iter().map(|r| r.seq()).collect();
+        let contigs = if self.fasta {
+            if reads.is_empty() {
+                return Err(AssemblyError::NoReads.into());
+            }
+            assembly::assemble_contigs(&reads, min_overlap)
+        } else {
+            vec![assembly::assemble_one(&reads, min_overlap)?]
+        };
+        let layout = self
+            .layout
+            .as_ref()
+            .map(|_| report::layout_tsv(&input, &contigs));
+        let report = if self.fasta {
+            Report::Contigs(report::contig_records(&input, &contigs))
+        } else {
+            let first = &input[contigs[0].layout[0].read];
+            Report::Superstring(fasta::Record::with_attrs(first.id(), None, &contigs[0].seq))
+        };
+        Ok(Assembly { report, layout })
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        match &output.report {
+            Report::Superstring(record) => str::from_utf8(record.seq()).unwrap().to_string(),
+            Report::Contigs(records) => report::to_fasta(records).trim_end().to_string(),
+            Report::Gfa(gfa) => gfa.trim_end().to_string(),
+        }
+    }
+
+    /// Writes the layout to the `--layout` file, if one was asked for
+    fn export(&self, output: &Self::Output) -> Result<(), Error> {
+        if let (Some(path), Some(layout)) = (&self.layout, &output.layout) {
+            fs::write(path, layout)
+                .map_err(|e| format!("cannot write layout to {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Any superstring of every read with the same length as the expected
+    ///  answer is accepted, since ties between overlaps can be broken either way
+    fn check(&self, input: &Self::Input, expected: &str, actual: &str) -> bool {
+        let actual = actual.trim();
+        actual.len() == expected.trim().len()
+            && input
+                .iter()
+                .all(|r| actual.contains(str::from_utf8(r.seq()).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::Solver;
+    use std::env;
+
+    const READS: &str = ">a\nATTAGACCTG\n>b\nCCTGCCGGAA\n>c\nAGACCTGCCG\n>d\nGCCGGAATAC\n";
+
+    #[test]
+    fn layout_is_solved_without_touching_the_file() {
+        let path = env::temp_dir().join(format!("long-unsolved-{}.tsv", std::process::id()));
+        let long = Long {
+            layout: Some(path.clone()),
+            ..Long::default()
+        };
+        let output = long.solve(long.parse(READS).unwrap()).unwrap();
+        assert_eq!(long.format(&output), "ATTAGACCTGCCGGAATAC");
+        assert_eq!(output.layout.unwrap().lines().count(), 5);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn layout_file_is_written_when_run() {
+        let path = env::temp_dir().join(format!("long-layout-{}.tsv", std::process::id()));
+        let long = Long {
+            layout: Some(path.clone()),
+            ..Long::default()
+        };
+        assert_eq!(long.run(READS).unwrap(), "ATTAGACCTGCCGGAATAC");
+        let layout = fs::read_to_string(&path).unwrap();
+        assert_eq!(layout.lines().count(), 5);
+        fs::remove_file(&path).unwrap();
+
+        let unwritable = Long {
+            layout: Some(env::temp_dir().join("no-such-dir").join("layout.tsv")),
+            ..Long::default()
+        };
+        let err = unwritable.run(READS).unwrap_err();
+        assert!(err.to_string().starts_with("cannot write layout to "));
+    }
+
+    #[test]
+    fn no_layout_is_reported_unless_asked_for() {
+        let long = Long::default();
+        let output = long.solve(long.parse(READS).unwrap()).unwrap();
+        assert_eq!(output.layout, None);
+    }
+}
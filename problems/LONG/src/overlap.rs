@@ -0,0 +1,188 @@
+//! An index of every suffix-prefix overlap and containment among a set of
+//!  reads, built in one pass with Aho-Corasick automata rather than by
+//!  searching each pair of reads in turn
+
+use std::collections::HashMap;
+
+use aho_corasick::AhoCorasick;
+
+/// The suffix of read `from` matches the prefix of read `to` over `len`
+///  bases
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Overlap {
+    pub from: usize,
+    pub to: usize,
+    pub len: usize,
+}
+
+/// Read `read` occurs in full inside read `container`, starting at `offset`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Containment {
+    pub read: usize,
+    pub container: usize,
+    pub offset: usize,
+}
+
+/// The reads that no other read contains, and where each remaining read
+///  sits inside one of them. Of several identical reads the first is kept.
+///
+/// Arguments:
+/// * `reads`: The read sequences
+pub fn find_containments(reads: &[&[u8]]) -> (Vec<usize>, Vec<Containment>) {
+    let mut first_copy: HashMap<&[u8], usize> = HashMap::new();
+    let mut contained: Vec<Option<Containment>> = vec![None; reads.len()];
+    for (i, seq) in reads.iter().enumerate() {
+        let container = *first_copy.entry(seq).or_insert(i);
+        if container != i {
+            contained[i] = Some(Containment {
+                read: i,
+                container,
+                offset: 0,
+            });
+        }
+    }
+    let distinct: Vec<usize> = (0..reads.len())
+        .filter(|&i| contained[i].is_none())
+        .collect();
+
+    // A distinct read inside another is strictly shorter, so every read
+    //  inside a contained read is also inside the longer read containing
+    //  that one, and scanning every distinct read finds a kept container
+    let automaton =
+        AhoCorasick::new(distinct.iter().map(|&i| reads[i])).expect("reads form a valid automaton");
+    for &i in distinct.iter() {
+        for m in automaton.find_overlapping_iter(reads[i]) {
+            let j = distinct[m.pattern().as_usize()];
+            if j != i && reads[j].len() < reads[i].len() && contained[j].is_none() {
+                contained[j] = Some(Containment {
+                    read: j,
+                    container: i,
+                    offset: m.start(),
+                });
+            }
+        }
+    }
+
+    let mut kept = vec![];
+    let mut containments = vec![];
+    for (i, c) in contained.into_iter().enumerate() {
+        match c {
+            Some(c) => containments.push(c),
+            None => kept.push(i),
+        }
+    }
+    // A read might have been placed inside a container that turned out to
+    //  be contained itself; re-anchor it on that container's container
+    let placed: HashMap<usize, Containment> = containments.iter().map(|c| (c.read, *c)).collect();
+    for c in containments.iter_mut() {
+        while let Some(outer) = placed.get(&c.container) {
+            c.offset += outer.offset;
+            c.container = outer.container;
+        }
+    }
+    (kept, containments)
+}
+
+/// Every suffix-prefix overlap of at least `min_overlap` bases from one read
+///  onto a different one, up to the full length of either. Each read's
+///  first `min_overlap` bases serve as a seed; one scan of every read finds
+///  each place a seed starts, and only those are checked in full.
+///
+/// Arguments:
+/// * `reads`: The read sequences
+/// * `among`: The reads to consider, typically those `find_containments`
+///   keeps
+/// * `min_overlap`: The shortest overlap to report, at least 1
+pub fn find_overlaps(reads: &[&[u8]], among: &[usize], min_overlap: usize) -> Vec<Overlap> {
+    let min_overlap = min_overlap.max(1);
+    let mut seeds: Vec<&[u8]> = vec![];
+    let mut seeded: Vec<Vec<usize>> = vec![];
+    let mut seed_ids: HashMap<&[u8], usize> = HashMap::new();
+    for &j in among.iter().filter(|&&j| reads[j].len() >= min_overlap) {
+        let seed = &reads[j][..min_overlap];
+        let id = *seed_ids.entry(seed).or_insert_with(|| {
+            seeds.push(seed);
+            seeded.push(vec![]);
+            seeds.len() - 1
+        });
+        seeded[id].push(j);
+    }
+    if seeds.is_empty() {
+        return vec![];
+    }
+
+    let automaton = AhoCorasick::new(&seeds).expect("seeds form a valid automaton");
+    let mut overlaps = vec![];
+    for &i in among {
+        let seq = reads[i];
+        for m in automaton.find_overlapping_iter(seq) {
+            let suffix = &seq[m.start()..];
+            for &j in seeded[m.pattern().as_usize()].iter() {
+                if j != i && reads[j].starts_with(suffix) {
+                    overlaps.push(Overlap {
+                        from: i,
+                        to: j,
+                        len: suffix.len(),
+                    });
+                }
+            }
+        }
+    }
+    overlaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containments_are_anchored_on_kept_reads() {
+        let reads: [&[u8]; 4] = [b"CGTA", b"ACGTACGT", b"ACGTACGT", b"GTA"];
+        let (kept, containments) = find_containments(&reads);
+        assert_eq!(kept, [1]);
+        let mut containments: Vec<(usize, usize, usize)> = containments
+            .into_iter()
+            .map(|c| (c.read, c.container, c.offset))
+            .collect();
+        containments.sort();
+        assert_eq!(containments[1], (2, 1, 0));
+        for (read, container, offset) in containments {
+            assert_eq!(container, 1);
+            assert_eq!(&reads[1][offset..offset + reads[read].len()], reads[read]);
+        }
+    }
+
+    #[test]
+    fn every_overlap_of_the_minimum_length_or_more_is_found() {
+        let reads: [&[u8]; 3] = [b"AAATTT", b"TTTGGG", b"TTGGGA"];
+        let mut overlaps = find_overlaps(&reads, &[0, 1, 2], 2);
+        overlaps.sort();
+        assert_eq!(
+            overlaps,
+            [
+                Overlap {
+                    from: 0,
+                    to: 1,
+                    len: 2
+                },
+                Overlap {
+                    from: 0,
+                    to: 1,
+                    len: 3
+                },
+                Overlap {
+                    from: 0,
+                    to: 2,
+                    len: 2
+                },
+                Overlap {
+                    from: 1,
+                    to: 2,
+                    len: 5
+                },
+            ]
+        );
+        assert!(find_overlaps(&reads, &[0, 1, 2], 7).is_empty());
+        assert_eq!(find_overlaps(&reads, &[0, 2], 2).len(), 1);
+    }
+}
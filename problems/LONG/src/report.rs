@@ -0,0 +1,165 @@
+//! Contigs written out with their provenance, so an assembly can be traced
+//!  back to the reads it came from
+
+use std::fmt::Write;
+
+use bio::io::fasta::Writer;
+use rosalind_core::fasta;
+
+use crate::assembly::Contig;
+
+/// The header description of a contig: its length and each read as
+///  `id:offset:overlap`, in order of offset
+///
+/// Arguments:
+/// * `records`: The reads the contig was assembled from
+/// * `contig`: The contig to describe
+pub fn provenance(records: &[fasta::Record], contig: &Contig) -> String {
+    let reads = contig
+        .layout
+        .iter()
+        .map(|p| format!("{}:{}:{}", records[p.read].id(), p.offset, p.overlap))
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("length={} reads={}", contig.seq.len(), reads)
+}
+
+/// One FASTA record per contig, named `contig_1`, `contig_2`, ... with its
+///  provenance as the description
+///
+/// Arguments:
+/// * `records`: The reads the contigs were assembled from
+/// * `contigs`: The assembled contigs
+pub fn contig_records(records: &[fasta::Record], contigs: &[Contig]) -> Vec<fasta::Record> {
+    contigs
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            fasta::Record::with_attrs(
+                &format!("contig_{}", i + 1),
+                Some(&provenance(records, c)),
+                &c.seq,
+            )
+        })
+        .collect()
+}
+
+/// Render records as FASTA text
+///
+/// Arguments:
+/// * `records`: The records to write
+pub fn to_fasta(records: &[fasta::Record]) -> String {
+    let mut buf = Vec::new();
+    {
+        let mut writer = Writer::new(&mut buf);
+        for r in records {
+            writer.write_record(r).unwrap();
+        }
+        writer.flush().unwrap();
+    }
+    String::from_utf8(buf).unwrap()
+}
+
+/// A tab-separated layout with a header row and one row per read placed
+///  on a contig, giving its offset, length and overlap with the read
+///  before it
+///
+/// Arguments:
+/// * `records`: The reads the contigs were assembled from
+/// * `contigs`: The assembled contigs
+pub fn layout_tsv(records: &[fasta::Record], contigs: &[Contig]) -> String {
+    let mut out = String::from("contig\tread\toffset\tlength\toverlap\n");
+    for (i, c) in contigs.iter().enumerate() {
+        for p in c.layout.iter() {
+            let read = &records[p.read];
+            writeln!(
+                out,
+                "contig_{}\t{}\t{}\t{}\t{}",
+                i + 1,
+                read.id(),
+                p.offset,
+                read.seq().len(),
+                p.overlap
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembly::Placement;
+
+    fn example() -> (Vec<fasta::Record>, Vec<Contig>) {
+        let records = vec![
+            fasta::Record::with_attrs("r1", None, b"ACGTAC"),
+            fasta::Record::with_attrs("r2", None, b"TACGGA"),
+            fasta::Record::with_attrs("r3", None, b"CGTA"),
+            fasta::Record::with_attrs("r4", None, b"TTTT"),
+        ];
+        let contigs = vec![
+            Contig {
+                seq: b"ACGTACGGA".to_vec(),
+                layout: vec![
+                    Placement {
+                        read: 0,
+                        offset: 0,
+                        overlap: 0,
+                    },
+                    Placement {
+                        read: 2,
+                        offset: 1,
+                        overlap: 0,
+                    },
+                    Placement {
+                        read: 1,
+                        offset: 3,
+                        overlap: 3,
+                    },
+                ],
+            },
+            Contig {
+                seq: b"TTTT".to_vec(),
+                layout: vec![Placement {
+                    read: 3,
+                    offset: 0,
+                    overlap: 0,
+                }],
+            },
+        ];
+        (records, contigs)
+    }
+
+    #[test]
+    fn contigs_carry_their_reads() {
+        let (records, contigs) = example();
+        assert_eq!(
+            provenance(&records, &contigs[0]),
+            "length=9 reads=r1:0:0,r3:1:0,r2:3:3"
+        );
+        assert_eq!(
+            to_fasta(&contig_records(&records, &contigs)),
+            ">contig_1 length=9 reads=r1:0:0,r3:1:0,r2:3:3\nACGTACGGA\n\
+             >contig_2 length=4 reads=r4:0:0\nTTTT\n"
+        );
+    }
+
+    #[test]
+    fn layout_has_a_row_per_read() {
+        let (records, contigs) = example();
+        assert_eq!(
+            layout_tsv(&records, &contigs),
+            "contig\tread\toffset\tlength\toverlap\n\
+             contig_1\tr1\t0\t6\t0\n\
+             contig_1\tr3\t1\t4\t0\n\
+             contig_1\tr2\t3\t6\t3\n\
+             contig_2\tr4\t0\t4\t0\n"
+        );
+        assert_eq!(
+            layout_tsv(&records, &[]),
+            "contig\tread\toffset\tlength\toverlap\n"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Motif scans over a whole proteome, split across threads, with hits
+//!  written as TSV or GFF3 for annotation pipelines
+
+use std::fmt::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use rosalind_core::fasta;
+
+use crate::motif::Motif;
+
+/// One motif hit in one protein
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MotifHit {
+    /// The id of the protein's FASTA record
+    pub id: String,
+    /// The PROSITE pattern that matched
+    pub motif: String,
+    /// The 1-based position of the first residue matched
+    pub start: usize,
+    /// The 1-based position of the last residue matched
+    pub end: usize,
+    /// The residues from `start` to `end`
+    pub residues: String,
+}
+
+/// How batch hits are written out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HitFormat {
+    /// A header row, then `id`, `motif`, `start`, `end` and `residues`
+    ///  separated by tabs
+    #[default]
+    Tsv,
+    /// GFF3 features of type `polypeptide_motif`
+    Gff3,
+}
+
+impl FromStr for HitFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<HitFormat, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "tsv" => Ok(HitFormat::Tsv),
+            "gff3" | "gff" => Ok(HitFormat::Gff3),
+            _ => Err(format!("unknown hit format '{}'; expected tsv or gff3", s)),
+        }
+    }
+}
+
+/// Every hit of every motif in one protein, motif by motif
+fn scan_record(record: &fasta::Record, motifs: &[Motif]) -> Vec<MotifHit> {
+    let seq = record.seq();
+    motifs
+        .iter()
+        .flat_map(|motif| {
+            motif.hits(seq).into_iter().map(move |h| MotifHit {
+                id: record.id().to_string(),
+                motif: motif.pattern().to_string(),
+                start: h.start,
+                end: h.end,
+                residues: String::from_utf8_lossy(&seq[h.start - 1..h.end]).into_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Scan every protein for every motif, sharing the proteins out among
+///  `threads` workers. Hits come back in record order, then motif order,
+///  then by position, however many threads are used.
+///
+/// Arguments:
+/// * `records`: The proteins to scan
+/// * `motifs`: The motifs to search for
+/// * `threads`: The number of worker threads, at least 1
+pub fn scan(records: &[fasta::Record], motifs: &[Motif], threads: usize) -> Vec<MotifHit> {
+    let threads = threads.clamp(1, records.len().max(1));
+    if threads == 1 {
+        return records
+            .iter()
+            .flat_map(|r| scan_record(r, motifs))
+            .collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut per_record: Vec<(usize, Vec<MotifHit>)> = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        match records.get(i) {
+                            Some(record) => done.push((i, scan_record(record, motifs))),
+                            None => return done,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    });
+    per_record.sort_unstable_by_key(|&(i, _)| i);
+    per_record.into_iter().flat_map(|(_, hits)| hits).collect()
+}
+
+/// The number of threads to scan with when none is given
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Whether GFF3 requires `c` to be escaped in the seqid column, which only
+///  allows `[a-zA-Z0-9.:^*$@!+_?-|]` as is
+fn escape_in_seqid(c: char) -> bool {
+    !(c.is_ascii_alphanumeric() || ".:^*$@!+_?-|".contains(c))
+}
+
+/// Whether GFF3 requires `c` to be escaped in an attribute name or value
+fn escape_in_attribute(c: char) -> bool {
+    matches!(c, '%' | ';' | '=' | '&' | ',') || c.is_control()
+}
+
+/// Percent-encode each character of `text` for which `escape` holds
+fn gff3_escape(text: &str, escape: fn(char) -> bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if escape(c) {
+            let mut buf = [0u8; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                write!(out, "%{:02X}", b).unwrap();
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Write hits as TSV or GFF3
+///
+/// Arguments:
+/// * `hits`: The hits to write
+/// * `format`: The output format
+pub fn format_hits(hits: &[MotifHit], format: HitFormat) -> String {
+    let mut out = String::new();
+    match format {
+        HitFormat::Tsv => {
+            out.push_str("id\tmotif\tstart\tend\tresidues\n");
+            for h in hits {
+                writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}",
+                    h.id, h.motif, h.start, h.end, h.residues
+                )
+                .unwrap();
+            }
+        }
+        HitFormat::Gff3 => {
+            out.push_str("##gff-version 3\n");
+            for h in hits {
+                writeln!(
+                    out,
+                    "{}\tMPRT\tpolypeptide_motif\t{}\t{}\t.\t.\t.\tName={};residues={}",
+                    gff3_escape(&h.id, escape_in_seqid),
+                    h.start,
+                    h.end,
+                    gff3_escape(&h.motif, escape_in_attribute),
+                    gff3_escape(&h.residues, escape_in_attribute)
+                )
+                .unwrap();
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motif::N_GLYCOSYLATION;
+    use rosalind_core::testing::XorShift;
+
+    fn proteome(n: usize) -> Vec<fasta::Record> {
+        let mut rng = XorShift::new(0x2545f4914f6cdd1d);
+        (0..n)
+            .map(|i| {
+                let seq: Vec<u8> = (0..(i % 50)).map(|_| b"ACNPST"[rng.below(6)]).collect();
+                fasta::Record::with_attrs(&format!("p{}", i), None, &seq)
+            })
+            .filter(|r| !r.seq().is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn scan_is_independent_of_thread_count() {
+        let records = proteome(500);
+        let motifs = vec![
+            Motif::new(N_GLYCOSYLATION).unwrap(),
+            Motif::new("C-x(2,4)-C").unwrap(),
+        ];
+        let serial = scan(&records, &motifs, 1);
+        assert!(!serial.is_empty());
+        assert_eq!(scan(&records, &motifs, 4), serial);
+        assert_eq!(scan(&records, &motifs, 64), serial);
+        assert!(scan(&[], &motifs, 4).is_empty());
+    }
+
+    #[test]
+    fn formats_are_parsed_by_name() {
+        assert_eq!("TSV".parse(), Ok(HitFormat::Tsv));
+        assert_eq!("gff".parse(), Ok(HitFormat::Gff3));
+        assert!("bed".parse::<HitFormat>().is_err());
+    }
+
+    #[test]
+    fn gff3_escapes_seqids_and_attributes() {
+        let hit = MotifHit {
+            id: String::from("sp|P1|A;B=C&D,E F%>"),
+            motif: String::from("C-x(2,4)-C"),
+            start: 4,
+            end: 7,
+            residues: String::from("CAAC"),
+        };
+        assert_eq!(
+            format_hits(&[hit], HitFormat::Gff3),
+            "##gff-version 3\n\
+             sp|P1|A%3BB%3DC%26D%2CE%20F%25%3E\tMPRT\tpolypeptide_motif\t4\t7\t.\t.\t.\t\
+             Name=C-x(2%2C4)-C;residues=CAAC\n"
+        );
+        assert_eq!(
+            gff3_escape("chr1.2:a^b*c$d@e!f+g_h?i-j|k", escape_in_seqid),
+            "chr1.2:a^b*c$d@e!f+g_h?i-j|k"
+        );
+        assert_eq!(gff3_escape("a\tb", escape_in_attribute), "a%09b");
+    }
+}
@@ -0,0 +1,291 @@
+pub mod batch;
+pub mod motif;
+pub mod source;
+
+use std::path::PathBuf;
+
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Options, Problem};
+
+pub use batch::{HitFormat, MotifHit};
+pub use motif::{Hit, Motif, MotifError};
+pub use source::{CachedSource, FetchError, HttpSource, LocalDirectory, ProteinSource};
+
+/// Search every protein in a FASTA file for a motif and return each record
+///  id with the 1-based start of every hit, overlapping ones included, in
+///  the order the records appear. Proteins without a hit have an empty
+///  list.
+///
+/// Arguments:
+/// * `fasta_text`: FASTA text containing the protein sequences to search
+/// * `motif`: The motif to search for
+pub fn identify_motifs(
+    fasta_text: &str,
+    motif: &Motif,
+) -> Result<Vec<(String, Vec<usize>)>, fasta::FastaError> {
+    let records = fasta::parse_fasta(fasta_text.as_bytes(), Alphabet::Protein)?;
+    Ok(records
+        .iter()
+        .map(|record| (record.id().to_string(), motif.positions(record.seq())))
+        .collect())
+}
+
+/// Search every protein in a FASTA file for N-glycosylation motifs and
+///  return each record id with their locations, in input order
+///
+/// Arguments:
+/// * `fasta_text`: FASTA text containing the protein sequences to search
+pub fn identify_nglyco_motifs(
+    fasta_text: &str,
+) -> Result<Vec<(String, Vec<usize>)>, fasta::FastaError> {
+    let motif = Motif::new(motif::N_GLYCOSYLATION).unwrap();
+    identify_motifs(fasta_text, &motif)
+}
+
+/// What MPRT reads from its dataset
+#[derive(Clone, Debug)]
+pub enum Dataset {
+    /// UniProt IDs to fetch, one per line
+    Queries(Vec<String>),
+    /// Proteins to scan directly, in batch mode
+    Proteins(Vec<fasta::Record>),
+}
+
+/// What MPRT reports for a dataset
+#[derive(Clone, Debug)]
+pub enum Report {
+    /// Each protein with a hit and the 1-based start of every hit
+    Positions(Vec<(String, Vec<usize>)>),
+    /// Every hit of every motif, in batch mode
+    Hits(Vec<MotifHit>),
+}
+
+/// Finding a Protein Motif
+#[derive(Default)]
+pub struct Mprt {
+    /// Read proteins from `<id>.fasta` files in this directory instead of
+    ///  over HTTP
+    pub local: Option<PathBuf>,
+    /// Keep fetched proteins in a content-addressed cache here
+    pub cache_dir: Option<PathBuf>,
+    /// Fetch `<accession>.fasta` from this URL instead of UniProt
+    pub base_url: Option<String>,
+    /// The PROSITE motifs to search for, or N-glycosylation if empty
+    pub motifs: Vec<Motif>,
+    /// Treat the dataset as protein FASTA to scan, rather than IDs to fetch
+    pub batch: bool,
+    /// How batch hits are written
+    pub format: HitFormat,
+    /// The number of threads a batch scan uses, or every core if `None`
+    pub threads: Option<usize>,
+}
+
+impl Mprt {
+    /// The source that `solve` fetches proteins from, as configured
+    pub fn source(&self) -> Result<Box<dyn ProteinSource>, Error> {
+        let inner: Box<dyn ProteinSource> = match &self.local {
+            Some(dir) => Box::new(LocalDirectory::new(dir)),
+            None => Box::new(HttpSource::new(
+                self.base_url.as_deref().unwrap_or(source::UNIPROT_BASE_URL),
+            )?),
+        };
+        Ok(match &self.cache_dir {
+            Some(dir) => Box::new(CachedSource::new(inner, dir)?),
+            None => inner,
+        })
+    }
+
+    /// The motifs that `solve` searches for, as configured
+    pub fn motifs(&self) -> Vec<Motif> {
+        if self.motifs.is_empty() {
+            vec![Motif::new(motif::N_GLYCOSYLATION).unwrap()]
+        } else {
+            self.motifs.clone()
+        }
+    }
+}
+
+impl Problem for Mprt {
+    type Input = Dataset;
+    type Output = Report;
+
+    fn id(&self) -> &'static str {
+        "MPRT"
+    }
+
+    /// `--local <dir>` reads proteins from FASTA files in a directory,
+    ///  `--base-url <url>` fetches them from a server other than UniProt,
+    ///  `--cache-dir <dir>` keeps what was fetched for next time, and
+    ///  `--motif <pattern>` searches for a PROSITE pattern other than
+    ///  N-glycosylation. `--batch` scans a protein FASTA dataset for every
+    ///  `--motif` given, on `--threads <n>` threads, and writes each hit in
+    ///  `--format tsv` or `gff3`.
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.motifs = options.take_all("motif")?;
+        self.local = options.take("local")?;
+        self.cache_dir = options.take("cache-dir")?;
+        self.base_url = options.take("base-url")?;
+        self.batch = options.take_flag("batch")?;
+        let format: Option<HitFormat> = options.take("format")?;
+        self.threads = options.take("threads")?;
+        if self.local.is_some() && self.base_url.is_some() {
+            return Err("--local cannot be combined with --base-url".into());
+        }
+        if self.batch {
+            if self.local.is_some() || self.base_url.is_some() || self.cache_dir.is_some() {
+                return Err(
+                    "--batch scans the dataset itself and cannot be combined with --local, --base-url or --cache-dir"
+                        .into(),
+                );
+            }
+            if self.threads == Some(0) {
+                return Err("--threads must be at least 1".into());
+            }
+        } else if format.is_some() || self.threads.is_some() || self.motifs.len() > 1 {
+            return Err("--format, --threads and more than one --motif need --batch".into());
+        }
+        self.format = format.unwrap_or_default();
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        if self.batch {
+            return Ok(Dataset::Proteins(fasta::parse_fasta(
+                input.as_bytes(),
+                Alphabet::Protein,
+            )?));
+        }
+        Ok(Dataset::Queries(
+            input
+                .lines()
+                .map(|l| l.trim_end())
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let input = match input {
+            Dataset::Queries(queries) => queries,
+            Dataset::Proteins(records) => {
+                let threads = self.threads.unwrap_or_else(batch::default_threads);
+                return Ok(Report::Hits(batch::scan(&records, &self.motifs(), threads)));
+            }
+        };
+        let source = self.source()?;
+        let motif = self.motifs().remove(0);
+        let mut hits = vec![];
+        for line in input {
+            let uniprot_data = source.fetch(&line)?;
+            let by_record = identify_motifs(&uniprot_data, &motif)?;
+
+            // A single entry is reported under the name it was asked for,
+            //  and each protein of a multi-record file under its own id
+            if by_record.len() == 1 {
+                let (_, pos) = by_record.into_iter().next().unwrap();
+                if !pos.is_empty() {
+                    hits.push((line, pos));
+                }
+            } else {
+                hits.extend(by_record.into_iter().filter(|(_, pos)| !pos.is_empty()));
+            }
+        }
+        Ok(Report::Positions(hits))
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        let output = match output {
+            Report::Positions(output) => output,
+            Report::Hits(hits) => {
+                return batch::format_hits(hits, self.format).trim_end().to_string()
+            }
+        };
+        output
+            .iter()
+            .map(|(id, pos)| {
+                let pos_str = pos
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                format!("{}\n{}", id, pos_str)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    use rosalind_core::testing::configured;
+    use rosalind_core::Solver;
+
+    #[test]
+    fn every_record_is_scanned_in_order() {
+        let fasta = ">short\nNS\n>tail\nAANASA\n>none\nPPPP\n>first\nNGSA\n>tail\nNAT\n";
+        let expected: Vec<(String, Vec<usize>)> = [
+            ("short", vec![]),
+            ("tail", vec![3]),
+            ("none", vec![]),
+            ("first", vec![1]),
+            ("tail", vec![]),
+        ]
+        .into_iter()
+        .map(|(id, pos)| (id.to_string(), pos))
+        .collect();
+        assert_eq!(identify_nglyco_motifs(fasta).unwrap(), expected);
+    }
+
+    #[test]
+    fn solver_reports_each_record_of_a_proteome() {
+        let dir = env::temp_dir().join(format!("mprt-proteome-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("proteome.fasta"),
+            ">b\nNATA\n>a\nMNKTANQSP\n>c\nNPS\n",
+        )
+        .unwrap();
+        let solver = Mprt {
+            local: Some(dir.clone()),
+            ..Mprt::default()
+        };
+        assert_eq!(solver.run("proteome\n").unwrap(), "b\n1\na\n2");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn batch_mode_writes_tsv_and_gff3() {
+        let fasta = ">sp|P1|ONE\nNASCAAC\n>P2\nPPPP\n";
+        let mut solver: Mprt =
+            configured(&["--batch", "--motif", "N-{P}-[ST]", "--motif", "C-x(2,4)-C"]).unwrap();
+        assert_eq!(
+            solver.run(fasta).unwrap(),
+            "id\tmotif\tstart\tend\tresidues\n\
+             sp|P1|ONE\tN-{P}-[ST]\t1\t3\tNAS\n\
+             sp|P1|ONE\tC-x(2,4)-C\t4\t7\tCAAC"
+        );
+
+        solver.format = HitFormat::Gff3;
+        assert_eq!(
+            solver.run(fasta).unwrap(),
+            "##gff-version 3\n\
+             sp|P1|ONE\tMPRT\tpolypeptide_motif\t1\t3\t.\t.\t.\tName=N-{P}-[ST];residues=NAS\n\
+             sp|P1|ONE\tMPRT\tpolypeptide_motif\t4\t7\t.\t.\t.\tName=C-x(2%2C4)-C;residues=CAAC"
+        );
+    }
+
+    #[test]
+    fn batch_options_are_checked() {
+        assert!(configured::<Mprt>(&["--batch", "--threads", "2", "--format", "gff3"]).is_ok());
+        assert!(configured::<Mprt>(&["--format", "tsv"]).is_err());
+        assert!(configured::<Mprt>(&["--motif", "N", "--motif", "S"]).is_err());
+        assert!(configured::<Mprt>(&["--batch", "--local", "data"]).is_err());
+        assert!(configured::<Mprt>(&["--batch", "--threads", "0"]).is_err());
+        assert!(configured::<Mprt>(&["--batch", "--format", "bed"]).is_err());
+    }
+}
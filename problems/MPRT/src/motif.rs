@@ -0,0 +1,473 @@
+//! Protein motifs written in PROSITE pattern syntax, e.g. `N-{P}-[ST]-{P}`,
+//!  matched at every position so that overlapping hits are all reported
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The N-glycosylation motif that Rosalind MPRT searches for
+pub const N_GLYCOSYLATION: &str = "N-{P}-[ST]-{P}";
+
+/// Why a PROSITE pattern could not be compiled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MotifError {
+    /// The pattern has no elements
+    Empty,
+    /// An element is not `x`, a residue, `[..]` or `{..}`
+    InvalidElement { element: usize, text: String },
+    /// A repeat is not `(n)` or `(n,m)` with n <= m
+    InvalidRepeat { element: usize, text: String },
+    /// A `<` or `>` anchor is somewhere other than the start or end
+    MisplacedAnchor { element: usize },
+}
+
+impl fmt::Display for MotifError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MotifError::Empty => write!(f, "the pattern has no elements"),
+            MotifError::InvalidElement { element, text } => {
+                write!(
+                    f,
+                    "element {} ('{}') is not a valid residue class",
+                    element, text
+                )
+            }
+            MotifError::InvalidRepeat { element, text } => write!(
+                f,
+                "element {} has an invalid repeat '{}'; expected (n) or (n,m) with n <= m",
+                element, text
+            ),
+            MotifError::MisplacedAnchor { element } => write!(
+                f,
+                "element {} has an anchor, but '<' may only start and '>' only end a pattern",
+                element
+            ),
+        }
+    }
+}
+
+impl error::Error for MotifError {}
+
+/// The residues a single position of a motif accepts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Class {
+    /// `x`: any residue
+    Any,
+    /// `A` or `[ST]`: one of the residues listed. `[ST>]` in a pattern's
+    ///  last element also accepts the end of the sequence.
+    OneOf { residues: Vec<u8>, or_end: bool },
+    /// `{P}`: any residue except those listed
+    NoneOf(Vec<u8>),
+}
+
+impl Class {
+    /// Whether `residue` belongs to the class (case-insensitive)
+    pub fn contains(&self, residue: u8) -> bool {
+        let residue = residue.to_ascii_uppercase();
+        match self {
+            Class::Any => true,
+            Class::OneOf { residues, .. } => residues.contains(&residue),
+            Class::NoneOf(residues) => !residues.contains(&residue),
+        }
+    }
+}
+
+/// One element of a motif: a class repeated between `min` and `max` times
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Element {
+    pub class: Class,
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Where a motif occurs in a sequence
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hit {
+    /// The 1-based position of the first residue matched
+    pub start: usize,
+    /// The 1-based position of the last residue of the shortest match
+    ///  starting there
+    pub end: usize,
+}
+
+/// A compiled PROSITE pattern
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Motif {
+    pattern: String,
+    elements: Vec<Element>,
+    anchored_start: bool,
+    anchored_end: bool,
+    /// The most residues any match can cover
+    max_span: usize,
+}
+
+fn parse_residues(text: &str) -> Option<Vec<u8>> {
+    let mut residues: Vec<u8> = text.bytes().map(|b| b.to_ascii_uppercase()).collect();
+    if residues.is_empty() || !residues.iter().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    residues.sort_unstable();
+    residues.dedup();
+    Some(residues)
+}
+
+fn parse_repeat(text: &str) -> Option<(usize, usize)> {
+    let (min, max) = match text.split_once(',') {
+        Some((min, max)) => (min.trim().parse().ok()?, max.trim().parse().ok()?),
+        None => {
+            let n = text.trim().parse().ok()?;
+            (n, n)
+        }
+    };
+    if min <= max && max > 0 {
+        Some((min, max))
+    } else {
+        None
+    }
+}
+
+impl Motif {
+    /// Compile a PROSITE pattern: elements separated by `-`, each `x`, a
+    ///  residue, `[..]` or `{..}`, optionally repeated with `(n)` or
+    ///  `(n,m)`, with `<` and `>` anchoring the pattern to the start and end
+    ///  of the sequence. A trailing `.` is ignored.
+    ///
+    /// Arguments:
+    /// * `pattern`: The PROSITE pattern
+    pub fn new(pattern: &str) -> Result<Motif, MotifError> {
+        let pattern = pattern.trim();
+        let body = pattern.strip_suffix('.').unwrap_or(pattern);
+        if body.is_empty() {
+            return Err(MotifError::Empty);
+        }
+        let parts: Vec<&str> = body.split('-').map(str::trim).collect();
+        let last = parts.len() - 1;
+        let mut motif = Motif {
+            pattern: pattern.to_string(),
+            elements: vec![],
+            anchored_start: false,
+            anchored_end: false,
+            max_span: 0,
+        };
+        for (i, &part) in parts.iter().enumerate() {
+            let element = i + 1;
+            let mut text = part;
+            if let Some(rest) = text.strip_prefix('<') {
+                if i != 0 {
+                    return Err(MotifError::MisplacedAnchor { element });
+                }
+                motif.anchored_start = true;
+                text = rest;
+            }
+            if let Some(rest) = text.strip_suffix('>') {
+                if i != last {
+                    return Err(MotifError::MisplacedAnchor { element });
+                }
+                motif.anchored_end = true;
+                text = rest;
+            }
+
+            let (class_text, (min, max)) = match text.strip_suffix(')') {
+                Some(rest) => {
+                    let open = rest.rfind('(').ok_or_else(|| MotifError::InvalidElement {
+                        element,
+                        text: part.to_string(),
+                    })?;
+                    let repeat = &rest[open + 1..];
+                    let bounds = parse_repeat(repeat).ok_or_else(|| MotifError::InvalidRepeat {
+                        element,
+                        text: format!("({})", repeat),
+                    })?;
+                    (&rest[..open], bounds)
+                }
+                None => (text, (1, 1)),
+            };
+
+            let invalid = || MotifError::InvalidElement {
+                element,
+                text: part.to_string(),
+            };
+            let class = if class_text == "x" || class_text == "X" {
+                Class::Any
+            } else if let Some(inner) = class_text
+                .strip_prefix('[')
+                .and_then(|t| t.strip_suffix(']'))
+            {
+                let or_end = inner.contains('>');
+                if or_end && i != last {
+                    return Err(MotifError::MisplacedAnchor { element });
+                }
+                Class::OneOf {
+                    residues: parse_residues(&inner.replace('>', "")).ok_or_else(invalid)?,
+                    or_end,
+                }
+            } else if let Some(inner) = class_text
+                .strip_prefix('{')
+                .and_then(|t| t.strip_suffix('}'))
+            {
+                Class::NoneOf(parse_residues(inner).ok_or_else(invalid)?)
+            } else if class_text.len() == 1 {
+                Class::OneOf {
+                    residues: parse_residues(class_text).ok_or_else(invalid)?,
+                    or_end: false,
+                }
+            } else if class_text.contains(['<', '>']) {
+                return Err(MotifError::MisplacedAnchor { element });
+            } else {
+                return Err(invalid());
+            };
+            motif.max_span = motif.max_span.saturating_add(max);
+            motif.elements.push(Element { class, min, max });
+        }
+        Ok(motif)
+    }
+
+    /// The pattern the motif was compiled from
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The motif's elements, in order
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// The 0-based end of the shortest non-empty match starting at `start`,
+    ///  found by tracking every position each element can finish at. Only
+    ///  the `max_span` residues from `start` can take part, so `reachable`
+    ///  and `next` cover just that window and are reused from call to call.
+    fn shortest_match(
+        &self,
+        seq: &[u8],
+        start: usize,
+        reachable: &mut Vec<bool>,
+        next: &mut Vec<bool>,
+    ) -> Option<usize> {
+        let end = seq.len().min(start.saturating_add(self.max_span));
+        let at_end = end == seq.len();
+        if self.anchored_end && !at_end {
+            return None;
+        }
+        let window = &seq[start..end];
+        reachable.clear();
+        reachable.resize(window.len() + 1, false);
+        reachable[0] = true;
+        let mut lowest = 0;
+        for element in self.elements.iter() {
+            next.clear();
+            next.resize(window.len() + 1, false);
+            for p in (lowest..=window.len()).filter(|&p| reachable[p]) {
+                if p == window.len() && at_end {
+                    if let Class::OneOf { or_end: true, .. } = element.class {
+                        next[p] = true;
+                    }
+                }
+                if element.min == 0 {
+                    next[p] = true;
+                }
+                for (n, &residue) in window[p..].iter().take(element.max).enumerate() {
+                    if !element.class.contains(residue) {
+                        break;
+                    }
+                    if n + 1 >= element.min {
+                        next[p + n + 1] = true;
+                    }
+                }
+            }
+            std::mem::swap(reachable, next);
+            lowest = reachable.iter().position(|&r| r)?;
+        }
+        if self.anchored_end {
+            return if !window.is_empty() && reachable[window.len()] {
+                Some(end)
+            } else {
+                None
+            };
+        }
+        (1..=window.len())
+            .find(|&p| reachable[p])
+            .map(|p| start + p)
+    }
+
+    /// Every position the motif matches, including overlapping ones
+    ///
+    /// Arguments:
+    /// * `seq`: The protein sequence to scan
+    pub fn hits(&self, seq: &[u8]) -> Vec<Hit> {
+        let starts = if self.anchored_start {
+            0..seq.len().min(1)
+        } else {
+            0..seq.len()
+        };
+        let (mut reachable, mut next) = (vec![], vec![]);
+        starts
+            .filter_map(|s| {
+                self.shortest_match(seq, s, &mut reachable, &mut next)
+                    .map(|e| Hit {
+                        start: s + 1,
+                        end: e,
+                    })
+            })
+            .collect()
+    }
+
+    /// The 1-based start of every hit, as Rosalind MPRT reports them
+    ///
+    /// Arguments:
+    /// * `seq`: The protein sequence to scan
+    pub fn positions(&self, seq: &[u8]) -> Vec<usize> {
+        self.hits(seq).iter().map(|h| h.start).collect()
+    }
+}
+
+impl FromStr for Motif {
+    type Err = MotifError;
+
+    fn from_str(s: &str) -> Result<Motif, MotifError> {
+        Motif::new(s)
+    }
+}
+
+impl fmt::Display for Motif {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::testing::XorShift;
+
+    #[test]
+    fn n_glycosylation_hits_overlap() {
+        let motif = Motif::new(N_GLYCOSYLATION).unwrap();
+        // NNSS matches at 1 and 2, and the motif ending on the last residue counts
+        assert_eq!(motif.positions(b"NNSSA"), vec![1, 2]);
+        assert_eq!(motif.positions(b"ANPSTNATA"), vec![6]);
+        assert_eq!(motif.positions(b"NST"), Vec::<usize>::new());
+        assert_eq!(motif.positions(b""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn repeats_report_the_shortest_match() {
+        let motif: Motif = "C-x(2,4)-C".parse().unwrap();
+        assert_eq!(
+            motif.hits(b"CAACAAC"),
+            vec![Hit { start: 1, end: 4 }, Hit { start: 4, end: 7 }]
+        );
+    }
+
+    #[test]
+    fn anchors_pin_the_sequence_ends() {
+        let start: Motif = "<M-x-K".parse().unwrap();
+        assert_eq!(start.positions(b"MAKMAK"), vec![1]);
+        let end: Motif = "K-x(2)>".parse().unwrap();
+        assert_eq!(end.positions(b"KAAKAA"), vec![4]);
+        let or_end: Motif = "A-[ST>]".parse().unwrap();
+        assert_eq!(or_end.positions(b"ASAGA"), vec![1, 5]);
+    }
+
+    #[test]
+    fn malformed_patterns_are_rejected() {
+        assert_eq!(Motif::new("."), Err(MotifError::Empty));
+        assert_eq!(
+            Motif::new("A-x(4,2)"),
+            Err(MotifError::InvalidRepeat {
+                element: 2,
+                text: String::from("(4,2)")
+            })
+        );
+        assert_eq!(
+            Motif::new("A-<S"),
+            Err(MotifError::MisplacedAnchor { element: 2 })
+        );
+    }
+
+    /// Every position a match of `elements` starting at `p` can end at,
+    ///  found by trying each repeat count in turn
+    fn ends(seq: &[u8], elements: &[Element], p: usize, out: &mut Vec<usize>) {
+        let Some((element, rest)) = elements.split_first() else {
+            out.push(p);
+            return;
+        };
+        if p == seq.len() {
+            if let Class::OneOf { or_end: true, .. } = element.class {
+                ends(seq, rest, p, out);
+            }
+        }
+        for count in element.min..=element.max {
+            if p + count > seq.len() {
+                break;
+            }
+            if seq[p..p + count].iter().all(|&r| element.class.contains(r)) {
+                ends(seq, rest, p + count, out);
+            }
+        }
+    }
+
+    fn brute_force(motif: &Motif, seq: &[u8]) -> Vec<Hit> {
+        let starts = if motif.anchored_start {
+            0..seq.len().min(1)
+        } else {
+            0..seq.len()
+        };
+        starts
+            .filter_map(|s| {
+                let mut out = vec![];
+                ends(seq, &motif.elements, s, &mut out);
+                let end = if motif.anchored_end {
+                    out.into_iter().find(|&e| e == seq.len() && e > s)
+                } else {
+                    out.into_iter().filter(|&e| e > s).min()
+                };
+                end.map(|end| Hit { start: s + 1, end })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn windowed_matching_agrees_with_brute_force() {
+        let mut rng = XorShift::new(0x2545f4914f6cdd1d);
+        let mut random = |n: usize| rng.below(n);
+        for _ in 0..3000 {
+            let count = 1 + random(4);
+            let mut parts: Vec<String> = (0..count)
+                .map(|i| {
+                    let mut part = match random(4) {
+                        0 => String::from("x"),
+                        1 => String::from(["A", "C", "N"][random(3)]),
+                        2 if i == count - 1 && random(2) == 0 => String::from("[AC>]"),
+                        2 => String::from("[AN]"),
+                        _ => String::from("{C}"),
+                    };
+                    match random(3) {
+                        0 => part.push_str(&format!("({})", 1 + random(3))),
+                        1 => {
+                            let min = random(3);
+                            part.push_str(&format!("({},{})", min, min + 1 + random(2)));
+                        }
+                        _ => {}
+                    }
+                    part
+                })
+                .collect();
+            if random(4) == 0 {
+                parts[0].insert(0, '<');
+            }
+            if random(4) == 0 && !parts[count - 1].contains('>') {
+                parts[count - 1].push('>');
+            }
+            let pattern = parts.join("-");
+            let motif = Motif::new(&pattern).unwrap();
+            let seq: Vec<u8> = (0..random(12)).map(|_| b"ACN"[random(3)]).collect();
+            assert_eq!(
+                motif.hits(&seq),
+                brute_force(&motif, &seq),
+                "{} on {}",
+                pattern,
+                String::from_utf8_lossy(&seq)
+            );
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Where MPRT gets its protein sequences from: a directory of FASTA files,
+//!  the UniProt REST API (or a stand-in for it), or a cache in front of
+//!  either
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The UniProt REST endpoint that serves `<accession>.fasta`
+pub const UNIPROT_BASE_URL: &str = "https://rest.uniprot.org/uniprotkb/";
+
+/// Why a protein could not be fetched
+#[derive(Debug)]
+pub enum FetchError {
+    /// The protein ID has characters that cannot name a file or URL path
+    InvalidQuery { query: String },
+    /// The source has no entry for the accession
+    NotFound { accession: String, source: String },
+    /// The server answered with an error status
+    Http { accession: String, status: u16 },
+    /// The request could not be made or its reply read
+    Network { accession: String, message: String },
+    /// Reading or writing local files failed
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::InvalidQuery { query } => write!(
+                f,
+                "'{}' is not a valid protein ID; expected letters, digits, '_', '.' and '-'",
+                query
+            ),
+            FetchError::NotFound { accession, source } => {
+                write!(f, "{} has no entry for {}", source, accession)
+            }
+            FetchError::Http { accession, status } => {
+                write!(
+                    f,
+                    "fetching {} failed with HTTP status {}",
+                    accession, status
+                )
+            }
+            FetchError::Network { accession, message } => {
+                write!(f, "fetching {} failed: {}", accession, message)
+            }
+            FetchError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> FetchError {
+        FetchError::Io(e)
+    }
+}
+
+/// The UniProt accession in an MPRT query, which may carry an entry name
+///  after an underscore, e.g. `P07204` for `P07204_TRBM_HUMAN`
+///
+/// Arguments:
+/// * `query`: The protein ID as given in the dataset
+pub fn accession(query: &str) -> &str {
+    query.split('_').next().unwrap_or(query)
+}
+
+/// Check that a protein ID is safe to use as a file name or URL path
+///  segment: one or more of `[A-Za-z0-9_.-]`, not starting with `.`, so it
+///  can neither name another directory nor climb out of one
+///
+/// Arguments:
+/// * `query`: The protein ID as given in the dataset
+pub fn validate_query(query: &str) -> Result<(), FetchError> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-');
+    if query.is_empty() || query.starts_with('.') || !query.chars().all(allowed) {
+        return Err(FetchError::InvalidQuery {
+            query: query.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Anything that can produce the FASTA text of a UniProt entry
+pub trait ProteinSource {
+    /// Arguments:
+    /// * `query`: The protein ID as given in the dataset
+    fn fetch(&self, query: &str) -> Result<String, FetchError>;
+}
+
+impl<S: ProteinSource + ?Sized> ProteinSource for Box<S> {
+    fn fetch(&self, query: &str) -> Result<String, FetchError> {
+        (**self).fetch(query)
+    }
+}
+
+/// FASTA files named after their protein, such as `data/MPRT/B5ZC00.fasta`
+#[derive(Clone, Debug)]
+pub struct LocalDirectory {
+    dir: PathBuf,
+}
+
+impl LocalDirectory {
+    /// Arguments:
+    /// * `dir`: The directory holding `<query>.fasta` or `<accession>.fasta`
+    pub fn new<P: AsRef<Path>>(dir: P) -> LocalDirectory {
+        LocalDirectory {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl ProteinSource for LocalDirectory {
+    fn fetch(&self, query: &str) -> Result<String, FetchError> {
+        validate_query(query)?;
+        for name in [query, accession(query)] {
+            let path = self.dir.join(format!("{}.fasta", name));
+            match fs::read_to_string(&path) {
+                Ok(text) => return Ok(text),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Err(FetchError::NotFound {
+            accession: accession(query).to_string(),
+            source: self.dir.display().to_string(),
+        })
+    }
+}
+
+/// The UniProt REST API, or any server laid out like it
+pub struct HttpSource {
+    base_url: String,
+    runtime: tokio::runtime::Runtime,
+    client: reqwest::Client,
+}
+
+impl HttpSource {
+    /// Arguments:
+    /// * `base_url`: The URL that `<accession>.fasta` is appended to
+    pub fn new(base_url: &str) -> Result<HttpSource, FetchError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let mut base_url = base_url.to_string();
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+        Ok(HttpSource {
+            base_url,
+            runtime,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// The public UniProt REST API
+    pub fn uniprot() -> Result<HttpSource, FetchError> {
+        HttpSource::new(UNIPROT_BASE_URL)
+    }
+}
+
+impl ProteinSource for HttpSource {
+    fn fetch(&self, query: &str) -> Result<String, FetchError> {
+        validate_query(query)?;
+        let accession = accession(query);
+        let url = format!("{}{}.fasta", self.base_url, accession);
+        let network = |e: reqwest::Error| FetchError::Network {
+            accession: accession.to_string(),
+            message: e.to_string(),
+        };
+        self.runtime.block_on(async {
+            let response = self.client.get(&url).send().await.map_err(network)?;
+            let status = response.status();
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(FetchError::NotFound {
+                    accession: accession.to_string(),
+                    source: self.base_url.clone(),
+                });
+            }
+            if !status.is_success() {
+                return Err(FetchError::Http {
+                    accession: accession.to_string(),
+                    status: status.as_u16(),
+                });
+            }
+            response.text().await.map_err(network)
+        })
+    }
+}
+
+/// A 64-bit FNV-1a digest, used to name cached entries after their
+///  contents. It only has to tell different entries apart, not resist
+///  tampering.
+fn digest(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Write `contents` to `path` by renaming a finished temporary file over it,
+///  so concurrent readers never see a partial write. Each write gets its own
+///  temporary file, so writers in other threads or processes cannot clash.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+    let tmp = path.with_extension(format!(
+        "tmp{}-{}",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// A content-addressed cache in front of another source. Each entry is
+///  stored once under `objects/<digest>.fasta`, and `refs/<accession>`
+///  names the digest for each protein fetched.
+pub struct CachedSource<S> {
+    inner: S,
+    dir: PathBuf,
+}
+
+impl<S: ProteinSource> CachedSource<S> {
+    /// Arguments:
+    /// * `inner`: The source to fetch from on a cache miss
+    /// * `dir`: The cache directory, created if missing
+    pub fn new<P: AsRef<Path>>(inner: S, dir: P) -> Result<CachedSource<S>, FetchError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join("objects"))?;
+        fs::create_dir_all(dir.join("refs"))?;
+        Ok(CachedSource { inner, dir })
+    }
+
+    fn object(&self, digest: &str) -> PathBuf {
+        self.dir.join("objects").join(format!("{}.fasta", digest))
+    }
+
+    /// The cached entry for `query`, if there is an intact one
+    fn lookup(&self, query: &str) -> Result<Option<String>, FetchError> {
+        let reference = self.dir.join("refs").join(accession(query));
+        let name = match fs::read_to_string(reference) {
+            Ok(name) => name.trim().to_string(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        match fs::read_to_string(self.object(&name)) {
+            // An object that no longer matches its name is treated as a miss
+            Ok(text) if digest(text.as_bytes()) == name => Ok(Some(text)),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<S: ProteinSource> ProteinSource for CachedSource<S> {
+    fn fetch(&self, query: &str) -> Result<String, FetchError> {
+        validate_query(query)?;
+        if let Some(text) = self.lookup(query)? {
+            return Ok(text);
+        }
+        let text = self.inner.fetch(query)?;
+        let name = digest(text.as_bytes());
+        write_atomic(&self.object(&name), text.as_bytes())?;
+        write_atomic(
+            &self.dir.join("refs").join(accession(query)),
+            name.as_bytes(),
+        )?;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::env;
+    use std::thread;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("mprt-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Serves a fixed entry and counts how often it is asked
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl ProteinSource for Counting {
+        fn fetch(&self, query: &str) -> Result<String, FetchError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!(">{}\nMNKT\n", query))
+        }
+    }
+
+    #[test]
+    fn queries_must_be_plain_names() {
+        for ok in ["P07204_TRBM_HUMAN", "B5ZC00", "Q9-2.fa"] {
+            assert!(validate_query(ok).is_ok(), "{}", ok);
+        }
+        for bad in ["", "..", ".hidden", "../etc/passwd", "a/b", "a b", "P0?x=1"] {
+            assert!(
+                matches!(validate_query(bad), Err(FetchError::InvalidQuery { .. })),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn local_directory_stays_inside_its_directory() {
+        let dir = scratch("local");
+        fs::write(dir.join("B5ZC00.fasta"), ">B5ZC00\nMNKT\n").unwrap();
+        fs::write(dir.join("secret.fasta"), ">s\nM\n").unwrap();
+        let local = LocalDirectory::new(dir.join("sub"));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        assert!(matches!(
+            local.fetch("../secret"),
+            Err(FetchError::InvalidQuery { .. })
+        ));
+        let local = LocalDirectory::new(&dir);
+        assert_eq!(local.fetch("B5ZC00_HUMAN").unwrap(), ">B5ZC00\nMNKT\n");
+        assert!(matches!(
+            local.fetch("P12345"),
+            Err(FetchError::NotFound { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cache_fetches_each_accession_once() {
+        let dir = scratch("cache");
+        let cache = CachedSource::new(
+            Counting {
+                calls: Cell::new(0),
+            },
+            &dir,
+        )
+        .unwrap();
+        assert_eq!(cache.fetch("P1_A").unwrap(), ">P1_A\nMNKT\n");
+        assert_eq!(cache.fetch("P1_A").unwrap(), ">P1_A\nMNKT\n");
+        assert_eq!(cache.inner.calls.get(), 1);
+        assert!(cache.fetch("..").is_err());
+        assert_eq!(cache.inner.calls.get(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn concurrent_writes_do_not_share_a_temporary_file() {
+        let dir = scratch("atomic");
+        let path = dir.join("entry");
+        thread::scope(|s| {
+            for t in 0..8 {
+                let path = &path;
+                s.spawn(move || {
+                    for _ in 0..50 {
+                        write_atomic(path, format!("writer {}", t).as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+        assert!(fs::read_to_string(&path).unwrap().starts_with("writer "));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
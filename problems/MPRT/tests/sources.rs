@@ -0,0 +1,138 @@
+//! Runs MPRT's protein sources against `data/MPRT` and a local stand-in for
+//!  the UniProt REST API, so none of these tests need the network
+
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use mprt::{CachedSource, FetchError, HttpSource, LocalDirectory, Mprt, ProteinSource};
+use rosalind_core::Solver;
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("data")
+        .join("MPRT")
+}
+
+/// An empty scratch directory unique to this test process
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("mprt-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Serve `data/MPRT/<accession>.fasta` over HTTP on a local port, returning
+///  its base URL and a count of the requests it has answered
+fn stand_in_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/uniprotkb/", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or("");
+            let name = path.trim_start_matches("/uniprotkb/");
+            let (status, body) = match fs::read_to_string(data_dir().join(name)) {
+                Ok(body) if !name.contains('/') => ("200 OK", body),
+                _ => ("404 Not Found", String::new()),
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn expected_fasta() -> String {
+    fs::read_to_string(data_dir().join("B5ZC00.fasta")).unwrap()
+}
+
+#[test]
+fn local_directory_strips_entry_names() {
+    let source = LocalDirectory::new(data_dir());
+    assert_eq!(source.fetch("B5ZC00").unwrap(), expected_fasta());
+    assert_eq!(source.fetch("B5ZC00_SYG_UREU1").unwrap(), expected_fasta());
+    assert!(matches!(
+        source.fetch("P12345"),
+        Err(FetchError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn http_source_fetches_from_base_url() {
+    let (base_url, requests) = stand_in_server();
+    let source = HttpSource::new(&base_url).unwrap();
+    assert_eq!(source.fetch("B5ZC00").unwrap(), expected_fasta());
+    assert!(matches!(
+        source.fetch("P12345"),
+        Err(FetchError::NotFound { .. })
+    ));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn cached_source_only_fetches_once() {
+    let (base_url, requests) = stand_in_server();
+    let dir = scratch_dir("cache");
+    let source = CachedSource::new(HttpSource::new(&base_url).unwrap(), &dir).unwrap();
+    assert_eq!(source.fetch("B5ZC00").unwrap(), expected_fasta());
+    assert_eq!(source.fetch("B5ZC00_SYG_UREU1").unwrap(), expected_fasta());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // A fresh cache over the same directory still hits
+    let reopened = CachedSource::new(HttpSource::new(&base_url).unwrap(), &dir).unwrap();
+    assert_eq!(reopened.fetch("B5ZC00").unwrap(), expected_fasta());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn solver_runs_against_stand_in_server() {
+    let (base_url, _) = stand_in_server();
+    let dir = scratch_dir("solver");
+    let solver = Mprt {
+        base_url: Some(base_url),
+        cache_dir: Some(dir.clone()),
+        ..Mprt::default()
+    };
+    assert_eq!(
+        solver.run("B5ZC00\n").unwrap(),
+        "B5ZC00\n85 118 142 306 395"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn solver_runs_against_local_directory() {
+    let solver = Mprt {
+        local: Some(data_dir()),
+        ..Mprt::default()
+    };
+    assert_eq!(
+        solver.run("B5ZC00\n").unwrap(),
+        "B5ZC00\n85 118 142 306 395"
+    );
+}
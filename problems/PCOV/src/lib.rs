@@ -0,0 +1,36 @@
+use std::str;
+
+use long::debruijn::{self, is_rotation};
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::{Error, Problem};
+
+/// Genome Assembly with Perfect Coverage
+pub struct Pcov;
+
+impl Problem for Pcov {
+    type Input = Vec<fasta::Record>;
+    type Output = String;
+
+    fn id(&self) -> &'static str {
+        "PCOV"
+    }
+
+    /// One read per line, or FASTA
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_sequences(input, Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let genome = debruijn::assemble_circular(&input)?;
+        Ok(str::from_utf8(&genome)?.to_string())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output.clone()
+    }
+
+    /// A circular genome may be reported from any starting point
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        is_rotation(expected.trim().as_bytes(), actual.trim().as_bytes())
+    }
+}
@@ -0,0 +1,5 @@
+use pcov::Pcov;
+
+fn main() -> Result<(), rosalind_core::Error> {
+    rosalind_core::problem::run_main(&mut Pcov)
+}
@@ -0,0 +1,37 @@
+use num_bigint::BigUint;
+
+use lexf::arrangements;
+use rosalind_core::{Error, Problem};
+
+/// Enumerating Gene Orders
+pub struct Perm;
+
+impl Problem for Perm {
+    type Input = usize;
+    type Output = (BigUint, Vec<Vec<usize>>);
+
+    fn id(&self) -> &'static str {
+        "PERM"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.trim().parse::<usize>()?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let orders = arrangements::permutations(input)
+            .map(|p| p.into_iter().map(|i| i + 1).collect())
+            .collect();
+        Ok((arrangements::permutation_count(input), orders))
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        let (count, orders) = output;
+        lexf::format_listing(count, orders)
+    }
+
+    /// Any listing order is accepted
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        lexf::same_listing(expected, actual)
+    }
+}
@@ -0,0 +1,5 @@
+use perm::Perm;
+
+fn main() -> Result<(), rosalind_core::Error> {
+    rosalind_core::problem::run_main(&mut Perm)
+}
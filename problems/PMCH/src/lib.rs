@@ -0,0 +1,100 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use rosalind_core::fasta::{self, Alphabet};
+use rosalind_core::modular;
+use rosalind_core::{Error, Options, Problem};
+
+/// Compute `num!`, reducing each partial product if a modulus is given
+///
+/// Arguments:
+/// * `num`: The value to take the factorial of
+/// * `modulo`: Reduce the answer modulo this value, if given
+pub fn factorial(num: usize, modulo: Option<&BigUint>) -> BigUint {
+    (1..=num).fold(modular::reduce(BigUint::one(), modulo), |acc, i| {
+        modular::reduce(acc * i, modulo)
+    })
+}
+
+static A_BYTE: u8 = b'A';
+static G_BYTE: u8 = b'G';
+
+/// Count the perfect matchings of basepair edges in an RNA string's
+///  bonding graph, assuming equal A/U and G/C counts
+///
+/// Arguments:
+/// * `seq`: The RNA string to count matchings for
+/// * `modulo`: Reduce the answer modulo this value, if given
+pub fn perfect_matchings(seq: &[u8], modulo: Option<&BigUint>) -> BigUint {
+    let a_count = seq.iter().filter(|c| **c == A_BYTE).count();
+    let g_count = seq.iter().filter(|c| **c == G_BYTE).count();
+    let a_factorial = factorial(a_count, modulo);
+    let g_factorial = factorial(g_count, modulo);
+    modular::reduce(a_factorial * g_factorial, modulo)
+}
+
+/// Perfect Matchings and RNA Secondary Structures
+#[derive(Default)]
+pub struct Pmch {
+    /// Report each answer modulo this value
+    pub modulo: Option<BigUint>,
+}
+
+impl Problem for Pmch {
+    type Input = Vec<fasta::Record>;
+    type Output = Vec<BigUint>;
+
+    fn id(&self) -> &'static str {
+        "PMCH"
+    }
+
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        self.modulo = modular::take_modulo(options)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(fasta::parse_fasta(input.as_bytes(), Alphabet::Nucleotide)?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok(input
+            .iter()
+            .map(|r| perfect_matchings(r.seq(), self.modulo.as_ref()))
+            .collect())
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosalind_core::Solver;
+
+    fn dataset() -> String {
+        format!(">Rosalind_35\n{}GGCC\n", "AU".repeat(35))
+    }
+
+    #[test]
+    fn large_answers_are_exact() {
+        assert_eq!(
+            Pmch::default().run(&dataset()).unwrap(),
+            "20666295932772289859333302675046400000000"
+        );
+    }
+
+    #[test]
+    fn answers_can_be_reduced() {
+        let solver = Pmch {
+            modulo: Some(BigUint::from(1_000_003u32)),
+        };
+        assert_eq!(solver.run(&dataset()).unwrap(), "92376");
+    }
+}
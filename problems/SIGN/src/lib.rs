@@ -0,0 +1,37 @@
+use num_bigint::BigUint;
+
+use lexf::arrangements::{self, SignedPermutations};
+use rosalind_core::{Error, Problem};
+
+/// Enumerating Oriented Gene Orderings
+pub struct Sign;
+
+impl Problem for Sign {
+    type Input = usize;
+    type Output = (BigUint, Vec<Vec<isize>>);
+
+    fn id(&self) -> &'static str {
+        "SIGN"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.trim().parse::<usize>()?)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        Ok((
+            arrangements::signed_permutation_count(input),
+            SignedPermutations::new(input).collect(),
+        ))
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        let (count, orders) = output;
+        lexf::format_listing(count, orders)
+    }
+
+    /// Any listing order is accepted
+    fn check(&self, _input: &Self::Input, expected: &str, actual: &str) -> bool {
+        lexf::same_listing(expected, actual)
+    }
+}
@@ -0,0 +1,5 @@
+use sign::Sign;
+
+fn main() -> Result<(), rosalind_core::Error> {
+    rosalind_core::problem::run_main(&mut Sign)
+}
@@ -0,0 +1,313 @@
+//! The NCBI genetic codes, each given as NCBI publishes it: the amino acid
+//!  and start-codon flag of all 64 codons, with codons ordered by first,
+//!  second then third base, each running T, C, A, G
+
+/// One numbered NCBI translation table
+#[derive(Debug, PartialEq, Eq)]
+pub struct GeneticCode {
+    /// The NCBI table number, as used by `transl_table`
+    pub id: u8,
+    /// The NCBI name of the table
+    pub name: &'static str,
+    /// The amino acid for each codon, with `*` for stop
+    amino_acids: &'static [u8; 64],
+    /// `M` for each codon that can start translation, `*` for each that can
+    ///  stop it, and `-` otherwise
+    starts: &'static [u8; 64],
+}
+
+/// Every genetic code NCBI currently lists, in order of id
+pub static GENETIC_CODES: [GeneticCode; 26] = [
+    GeneticCode {
+        id: 1,
+        name: "Standard",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**--*----M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 2,
+        name: "Vertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        starts: b"----------**--------------------MMMM----------**---M------------",
+    },
+    GeneticCode {
+        id: 3,
+        name: "Yeast Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**----------------------MM---------------M------------",
+    },
+    GeneticCode {
+        id: 4,
+        name: "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--MM------**-------M------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 5,
+        name: "Invertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        starts: b"---M------**--------------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 6,
+        name: "Ciliate, Dasycladacean and Hexamita Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 9,
+        name: "Echinoderm and Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"----------**-----------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 10,
+        name: "Euplotid Nuclear",
+        amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**-----------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 11,
+        name: "Bacterial, Archaeal and Plant Plastid",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**--*----M------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 12,
+        name: "Alternative Yeast Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**--*----M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 13,
+        name: "Ascidian Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        starts: b"---M------**----------------------MM---------------M------------",
+    },
+    GeneticCode {
+        id: 14,
+        name: "Alternative Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"-----------*-----------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 16,
+        name: "Chlorophycean Mitochondrial",
+        amino_acids: b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------*---*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 21,
+        name: "Trematode Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        starts: b"----------**-----------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 22,
+        name: "Scenedesmus obliquus Mitochondrial",
+        amino_acids: b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"------*---*---*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 23,
+        name: "Thraustochytrium Mitochondrial",
+        amino_acids: b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--*-------**--*-----------------M--M---------------M------------",
+    },
+    GeneticCode {
+        id: 24,
+        name: "Rhabdopleuridae Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        starts: b"---M------**-------M---------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 25,
+        name: "Candidate Division SR1 and Gracilibacteria",
+        amino_acids: b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------**-----------------------M---------------M------------",
+    },
+    GeneticCode {
+        id: 26,
+        name: "Pachysolen tannophilus Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**--*----M---------------M----------------------------",
+    },
+    GeneticCode {
+        id: 27,
+        name: "Karyorelict Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 28,
+        name: "Condylostoma Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**--*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 29,
+        name: "Mesodinium Nuclear",
+        amino_acids: b"FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 30,
+        name: "Peritrich Nuclear",
+        amino_acids: b"FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"--------------*--------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 31,
+        name: "Blastocrithidia Nuclear",
+        amino_acids: b"FFLLSSSSYYEECCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"----------**-----------------------M----------------------------",
+    },
+    GeneticCode {
+        id: 32,
+        name: "Balanophoraceae Plastid",
+        amino_acids: b"FFLLSSSSYY*WCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        starts: b"---M------*---*----M------------MMMM---------------M------------",
+    },
+    GeneticCode {
+        id: 33,
+        name: "Cephalodiscidae Mitochondrial UAA-Tyr",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        starts: b"---M-------*-------M---------------M---------------M------------",
+    },
+];
+
+/// The position of a base in NCBI's T, C, A, G order (case-insensitive),
+///  reading RNA's U as T
+fn base_index(base: u8) -> Option<usize> {
+    match base.to_ascii_uppercase() {
+        b'T' | b'U' => Some(0),
+        b'C' => Some(1),
+        b'A' => Some(2),
+        b'G' => Some(3),
+        _ => None,
+    }
+}
+
+/// The position of a codon in an NCBI table, or `None` unless it is three
+///  of A, C, G and T or U
+///
+/// Arguments:
+/// * `codon`: The three bases of the codon
+pub fn codon_index(codon: &[u8]) -> Option<usize> {
+    match codon {
+        &[a, b, c] => Some(base_index(a)? * 16 + base_index(b)? * 4 + base_index(c)?),
+        _ => None,
+    }
+}
+
+/// The codon at a position in an NCBI table
+fn codon_at(index: usize) -> String {
+    [index / 16, index / 4 % 4, index % 4]
+        .iter()
+        .map(|&i| b"TCAG"[i] as char)
+        .collect()
+}
+
+impl GeneticCode {
+    /// The code with NCBI table number `id`, if there is one
+    ///
+    /// Arguments:
+    /// * `id`: The NCBI table number
+    pub fn by_id(id: u8) -> Option<&'static GeneticCode> {
+        GENETIC_CODES.iter().find(|c| c.id == id)
+    }
+
+    /// The standard code, NCBI table 1
+    pub fn standard() -> &'static GeneticCode {
+        &GENETIC_CODES[0]
+    }
+
+    /// The amino acid a codon encodes, `*` for stop, or `None` if the codon
+    ///  is not three of A, C, G and T or U
+    ///
+    /// Arguments:
+    /// * `codon`: The three bases of the codon
+    pub fn translate_codon(&self, codon: &[u8]) -> Option<u8> {
+        codon_index(codon).map(|i| self.amino_acids[i])
+    }
+
+    /// Whether translation can start at `codon`
+    ///
+    /// Arguments:
+    /// * `codon`: The three bases of the codon
+    pub fn is_start(&self, codon: &[u8]) -> bool {
+        codon_index(codon).is_some_and(|i| self.starts[i] == b'M')
+    }
+
+    /// Whether `codon` ends translation. Codons that only stop translation
+    ///  in some contexts, as in tables 27, 28 and 31, count as the amino
+    ///  acid they otherwise encode.
+    ///
+    /// Arguments:
+    /// * `codon`: The three bases of the codon
+    pub fn is_stop(&self, codon: &[u8]) -> bool {
+        codon_index(codon).is_some_and(|i| self.amino_acids[i] == b'*')
+    }
+
+    /// The codons translation can start at, in NCBI table order
+    pub fn start_codons(&self) -> Vec<String> {
+        (0..64)
+            .filter(|&i| self.starts[i] == b'M')
+            .map(codon_at)
+            .collect()
+    }
+
+    /// The codons that end translation, in NCBI table order
+    pub fn stop_codons(&self) -> Vec<String> {
+        (0..64)
+            .filter(|&i| self.amino_acids[i] == b'*')
+            .map(codon_at)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_looked_up_by_ncbi_id() {
+        assert_eq!(GENETIC_CODES.len(), 26);
+        assert_eq!(GeneticCode::by_id(1), Some(GeneticCode::standard()));
+        assert_eq!(
+            GeneticCode::by_id(2).unwrap().name,
+            "Vertebrate Mitochondrial"
+        );
+        assert_eq!(GeneticCode::by_id(7), None);
+        assert!(GENETIC_CODES.windows(2).all(|w| w[0].id < w[1].id));
+    }
+
+    #[test]
+    fn codons_translate_per_table() {
+        let standard = GeneticCode::standard();
+        let vertebrate_mito = GeneticCode::by_id(2).unwrap();
+        let yeast_mito = GeneticCode::by_id(3).unwrap();
+        assert_eq!(standard.translate_codon(b"TGA"), Some(b'*'));
+        assert_eq!(vertebrate_mito.translate_codon(b"TGA"), Some(b'W'));
+        assert_eq!(vertebrate_mito.translate_codon(b"aga"), Some(b'*'));
+        assert_eq!(yeast_mito.translate_codon(b"CTG"), Some(b'T'));
+        assert_eq!(standard.translate_codon(b"NTG"), None);
+        assert_eq!(standard.translate_codon(b"AT"), None);
+    }
+
+    #[test]
+    fn start_and_stop_codons_per_table() {
+        let standard = GeneticCode::standard();
+        assert_eq!(standard.start_codons(), vec!["TTG", "CTG", "ATG"]);
+        assert_eq!(standard.stop_codons(), vec!["TAA", "TAG", "TGA"]);
+        let bacterial = GeneticCode::by_id(11).unwrap();
+        assert!(bacterial.is_start(b"GTG"));
+        assert!(!standard.is_start(b"GTG"));
+        let vertebrate_mito = GeneticCode::by_id(2).unwrap();
+        assert_eq!(
+            vertebrate_mito.stop_codons(),
+            vec!["TAA", "TAG", "AGA", "AGG"]
+        );
+        assert!(!vertebrate_mito.is_stop(b"TGA"));
+    }
+}
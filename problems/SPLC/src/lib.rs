@@ -0,0 +1,131 @@
+pub mod genetic_code;
+pub mod translation;
+
+use std::str;
+use std::string::String;
+
+use rosalind_core::fasta::{self, Alphabet, Record};
+use rosalind_core::{Error, Options, Problem};
+
+pub use genetic_code::GeneticCode;
+pub use translation::{translate, StopMode, Translation, TranslationError};
+
+pub fn find_introns(template: &Record, introns: &[Record]) -> Vec<(usize, usize)> {
+    let mut retval = Vec::<(usize, usize)>::new();
+
+    let template_seq = str::from_utf8(template.seq()).unwrap();
+    for i in introns {
+        let intron = str::from_utf8(i.seq()).unwrap();
+        let mut v: Vec<(usize, usize)> = template_seq
+            .match_indices(intron)
+            .map(|(i, _)| (i, i + intron.len()))
+            .collect();
+        retval.append(&mut v);
+    }
+
+    retval.sort();
+    retval
+}
+
+pub fn splice_sequence(template: &Record, introns: Vec<(usize, usize)>) -> String {
+    let regions: Vec<_> = introns.into_iter().rev().collect();
+    //println!("{:?}", regions);
+
+    let mut seq = String::from_utf8(template.seq().to_vec()).unwrap();
+    let mut len = seq.len();
+    for r in regions {
+        let start = r.0;
+        let end = r.1;
+        let pre = seq[0..start].to_owned();
+        //let i = seq[start..end].to_owned();
+        let post = seq[end..len].to_owned();
+        seq = format!("{}{}", pre, post);
+        len = seq.len();
+        //println!("{} {} {}", pre, i, post);
+    }
+    seq
+}
+
+/// RNA Splicing
+pub struct Splc {
+    /// The genetic code the spliced exons are translated with
+    pub code: &'static GeneticCode,
+    /// Whether to stop at the first stop codon or translate through them
+    pub stops: StopMode,
+}
+
+impl Default for Splc {
+    fn default() -> Splc {
+        Splc {
+            code: GeneticCode::standard(),
+            stops: StopMode::default(),
+        }
+    }
+}
+
+impl Problem for Splc {
+    type Input = Vec<Record>;
+    type Output = String;
+
+    fn id(&self) -> &'static str {
+        "SPLC"
+    }
+
+    /// `--genetic-code <id>` translates with an NCBI table other than the
+    ///  standard code, e.g. 2 for vertebrate mitochondria, and
+    ///  `--translate-through` writes stop codons as `*` rather than ending
+    ///  the protein at the first one
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        if options.take_flag("translate-through")? {
+            self.stops = StopMode::Through;
+        }
+        if let Some(id) = options.take::<u8>("genetic-code")? {
+            self.code = GeneticCode::by_id(id)
+                .ok_or_else(|| format!("there is no NCBI genetic code {}", id))?;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let records = fasta::parse_fasta(input.as_bytes(), Alphabet::Nucleotide)?;
+        if records.is_empty() {
+            return Err("expected a template record followed by introns".into());
+        }
+        Ok(records)
+    }
+
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error> {
+        let template = &input[0];
+        let intron_seqs = &input[1..];
+        let introns = find_introns(template, intron_seqs);
+        let rna = splice_sequence(template, introns);
+        Ok(translate(rna.as_bytes(), self.code, self.stops)?.protein)
+    }
+
+    fn format(&self, output: &Self::Output) -> String {
+        output.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rosalind_core::testing::configured;
+    use rosalind_core::Solver;
+
+    #[test]
+    fn solver_takes_a_genetic_code() {
+        let input = ">exon\nATGTGAAGATAA\n";
+        assert_eq!(Splc::default().run(input).unwrap(), "M");
+        let solver: Splc = configured(&["--genetic-code", "2"]).unwrap();
+        assert_eq!(solver.run(input).unwrap(), "MW");
+        assert!(configured::<Splc>(&["--genetic-code", "7"]).is_err());
+    }
+
+    #[test]
+    fn solver_can_translate_through_stops() {
+        let solver: Splc = configured(&["--translate-through"]).unwrap();
+        assert_eq!(solver.run(">exon\nATGTGAAGATAA\n").unwrap(), "M*R*");
+    }
+}
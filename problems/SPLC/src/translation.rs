@@ -0,0 +1,198 @@
+//! Translation of DNA or RNA into protein under any genetic code, resolving
+//!  IUPAC ambiguity codes where every reading of a codon agrees
+
+use std::error;
+use std::fmt;
+
+use crate::genetic_code::GeneticCode;
+
+/// What translation does on reaching a stop codon
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StopMode {
+    /// End the protein before the first stop codon
+    #[default]
+    AtFirstStop,
+    /// Write `*` for each stop codon and carry on to the end
+    Through,
+}
+
+/// Why a sequence could not be translated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranslationError {
+    /// A base is neither a nucleotide nor an IUPAC ambiguity code
+    InvalidBase { position: usize, base: u8 },
+}
+
+impl fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranslationError::InvalidBase { position, base } => write!(
+                f,
+                "'{}' at position {} is not a nucleotide or IUPAC ambiguity code",
+                base.escape_ascii(),
+                position
+            ),
+        }
+    }
+}
+
+impl error::Error for TranslationError {}
+
+/// A translated sequence and how translation ended
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Translation {
+    /// The amino acids, with `X` for ambiguous codons and, when translating
+    ///  through stops, `*` for each stop codon
+    pub protein: String,
+    /// The 1-based position of the first base of the stop codon that ended
+    ///  translation, if one did
+    pub stop: Option<usize>,
+    /// The one or two bases left after the last whole codon, if translation
+    ///  reached the end of the sequence with some
+    pub partial_codon: Option<String>,
+}
+
+/// The nucleotides an IUPAC code stands for (case-insensitive), reading
+///  RNA's U as T
+fn expand(base: u8) -> Option<&'static [u8]> {
+    Some(match base.to_ascii_uppercase() {
+        b'A' => b"A",
+        b'C' => b"C",
+        b'G' => b"G",
+        b'T' | b'U' => b"T",
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"CG",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        b'N' => b"ACGT",
+        _ => return None,
+    })
+}
+
+/// The amino acid a possibly ambiguous codon encodes, or `X` if its
+///  readings disagree
+fn translate_ambiguous(code: &GeneticCode, codon: [&[u8]; 3]) -> u8 {
+    let mut amino_acid = None;
+    for &a in codon[0] {
+        for &b in codon[1] {
+            for &c in codon[2] {
+                let aa = code.translate_codon(&[a, b, c]);
+                match amino_acid {
+                    None => amino_acid = aa,
+                    Some(_) if amino_acid != aa => return b'X',
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    amino_acid.unwrap_or(b'X')
+}
+
+/// Translate a DNA or RNA sequence codon by codon from its first base
+///
+/// Arguments:
+/// * `seq`: The bases to translate, as nucleotides or IUPAC ambiguity
+///   codes in either case
+/// * `code`: The genetic code to translate with
+/// * `mode`: Whether to end at the first stop codon or translate through
+pub fn translate(
+    seq: &[u8],
+    code: &GeneticCode,
+    mode: StopMode,
+) -> Result<Translation, TranslationError> {
+    let mut protein = String::with_capacity(seq.len() / 3);
+    for (i, codon) in seq.chunks(3).enumerate() {
+        let mut bases: [&[u8]; 3] = [&[], &[], &[]];
+        for (j, &base) in codon.iter().enumerate() {
+            bases[j] = expand(base).ok_or(TranslationError::InvalidBase {
+                position: 3 * i + j + 1,
+                base,
+            })?;
+        }
+        if codon.len() < 3 {
+            return Ok(Translation {
+                protein,
+                stop: None,
+                partial_codon: Some(String::from_utf8_lossy(codon).to_ascii_uppercase()),
+            });
+        }
+        let aa = translate_ambiguous(code, bases);
+        if aa == b'*' && mode == StopMode::AtFirstStop {
+            return Ok(Translation {
+                protein,
+                stop: Some(3 * i + 1),
+                partial_codon: None,
+            });
+        }
+        protein.push(aa as char);
+    }
+    Ok(Translation {
+        protein,
+        stop: None,
+        partial_codon: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard(seq: &str, mode: StopMode) -> Result<Translation, TranslationError> {
+        translate(seq.as_bytes(), GeneticCode::standard(), mode)
+    }
+
+    #[test]
+    fn dna_and_rna_translate_alike() {
+        let dna = standard("ATGGCCtgg", StopMode::AtFirstStop).unwrap();
+        let rna = standard("AUGGCCugg", StopMode::AtFirstStop).unwrap();
+        assert_eq!(dna.protein, "MAW");
+        assert_eq!(dna, rna);
+    }
+
+    #[test]
+    fn stops_end_or_mark_the_protein() {
+        let stopped = standard("ATGTAAGGG", StopMode::AtFirstStop).unwrap();
+        assert_eq!(stopped.protein, "M");
+        assert_eq!(stopped.stop, Some(4));
+        let through = standard("ATGTAAGGG", StopMode::Through).unwrap();
+        assert_eq!(through.protein, "M*G");
+        assert_eq!(through.stop, None);
+    }
+
+    #[test]
+    fn trailing_partial_codons_are_reported() {
+        let t = standard("ATGGCCGa", StopMode::AtFirstStop).unwrap();
+        assert_eq!(t.protein, "MA");
+        assert_eq!(t.partial_codon.as_deref(), Some("GA"));
+        // A stop before the end means the partial codon is never reached
+        let t = standard("TGAGC", StopMode::AtFirstStop).unwrap();
+        assert_eq!(t.partial_codon, None);
+        assert_eq!(standard("", StopMode::Through).unwrap().protein, "");
+    }
+
+    #[test]
+    fn ambiguity_codes_resolve_when_unanimous() {
+        // GCN is always alanine, TAR and TRA always stop, and ATN may be I or M
+        let t = standard("GCNTARTRAATN", StopMode::Through).unwrap();
+        assert_eq!(t.protein, "A**X");
+        assert_eq!(standard("NNN", StopMode::Through).unwrap().protein, "X");
+    }
+
+    #[test]
+    fn invalid_bases_are_errors() {
+        assert_eq!(
+            standard("ATGGZC", StopMode::AtFirstStop),
+            Err(TranslationError::InvalidBase {
+                position: 5,
+                base: b'Z'
+            })
+        );
+        assert!(standard("AT-", StopMode::AtFirstStop).is_err());
+    }
+}
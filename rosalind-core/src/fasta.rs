@@ -0,0 +1,286 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use bio::io::fasta;
+
+pub use bio::io::fasta::Record;
+
+/// The set of symbols a FASTA record's sequence is allowed to contain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// IUPAC nucleotide codes, covering both DNA and RNA plus ambiguity codes
+    Nucleotide,
+    /// IUPAC amino acid codes, plus `*` for stop
+    Protein,
+}
+
+impl Alphabet {
+    /// Whether `symbol` is a member of this alphabet (case-insensitive)
+    pub fn contains(&self, symbol: u8) -> bool {
+        let symbol = symbol.to_ascii_uppercase();
+        match self {
+            Alphabet::Nucleotide => b"ACGTURYSWKMBDHVN-".contains(&symbol),
+            Alphabet::Protein => symbol.is_ascii_uppercase() || symbol == b'*' || symbol == b'-',
+        }
+    }
+}
+
+/// Everything that can go wrong while loading FASTA records
+#[derive(Debug)]
+pub enum FastaError {
+    /// The input file does not exist
+    FileNotFound(PathBuf),
+    /// Any other I/O failure while reading the input
+    Io(io::Error),
+    /// A record header was missing its `>` or had no identifier
+    MalformedHeader { record: usize, message: String },
+    /// A record had a header but no sequence lines
+    EmptySequence { id: String },
+    /// A sequence contained a byte outside the requested alphabet
+    InvalidSymbol {
+        id: String,
+        position: usize,
+        symbol: u8,
+        alphabet: Alphabet,
+    },
+}
+
+impl fmt::Display for FastaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FastaError::FileNotFound(path) => {
+                write!(f, "FASTA file '{}' does not exist", path.display())
+            }
+            FastaError::Io(e) => write!(f, "unable to read FASTA input: {}", e),
+            FastaError::MalformedHeader { record, message } => {
+                write!(
+                    f,
+                    "malformed header for FASTA record {}: {}",
+                    record, message
+                )
+            }
+            FastaError::EmptySequence { id } => {
+                write!(f, "FASTA record '{}' has an empty sequence", id)
+            }
+            FastaError::InvalidSymbol {
+                id,
+                position,
+                symbol,
+                alphabet,
+            } => write!(
+                f,
+                "FASTA record '{}' has invalid {:?} symbol '{}' at position {}",
+                id,
+                alphabet,
+                symbol.escape_ascii(),
+                position
+            ),
+        }
+    }
+}
+
+impl Error for FastaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FastaError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Check a single record's header and sequence against `alphabet`
+///
+/// Arguments:
+/// * `record`: The record to validate
+/// * `index`: The 1-based position of the record in its file, for diagnostics
+/// * `alphabet`: The symbols the sequence may contain
+pub fn validate_record(
+    record: &Record,
+    index: usize,
+    alphabet: Alphabet,
+) -> Result<(), FastaError> {
+    if record.id().is_empty() {
+        return Err(FastaError::MalformedHeader {
+            record: index,
+            message: String::from("missing record identifier"),
+        });
+    }
+    if record.seq().is_empty() {
+        return Err(FastaError::EmptySequence {
+            id: record.id().to_string(),
+        });
+    }
+    if let Some(p) = record.seq().iter().position(|&b| !alphabet.contains(b)) {
+        return Err(FastaError::InvalidSymbol {
+            id: record.id().to_string(),
+            position: p + 1,
+            symbol: record.seq()[p],
+            alphabet,
+        });
+    }
+    Ok(())
+}
+
+/// Parse and validate every FASTA record from an arbitrary reader
+///
+/// Arguments:
+/// * `reader`: Source of FASTA-formatted text
+/// * `alphabet`: The symbols each record's sequence may contain
+pub fn parse_fasta<R: Read>(reader: R, alphabet: Alphabet) -> Result<Vec<Record>, FastaError> {
+    let reader = fasta::Reader::new(BufReader::new(reader));
+    let mut records = vec![];
+    for (i, result) in reader.records().enumerate() {
+        let record = result.map_err(|e| match e.kind() {
+            io::ErrorKind::Other => FastaError::MalformedHeader {
+                record: i + 1,
+                message: e.to_string(),
+            },
+            _ => FastaError::Io(e),
+        })?;
+        validate_record(&record, i + 1, alphabet)?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Parse sequences given either as FASTA or, when the text does not start
+///  with a `>` header, as one bare sequence per non-empty line. Bare
+///  sequences are named after their 1-based line number.
+///
+/// Arguments:
+/// * `input`: The FASTA or line-per-sequence text
+/// * `alphabet`: The symbols each sequence may contain
+pub fn parse_sequences(input: &str, alphabet: Alphabet) -> Result<Vec<Record>, FastaError> {
+    let fasta = input.trim_start();
+    if fasta.starts_with('>') {
+        return parse_fasta(fasta.as_bytes(), alphabet);
+    }
+    let mut records = vec![];
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let record = Record::with_attrs(&(i + 1).to_string(), None, line.as_bytes());
+        validate_record(&record, records.len() + 1, alphabet)?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Load and validate every FASTA record in a file
+///
+/// Arguments:
+/// * `fasta_file`: Path to the FASTA file to read
+/// * `alphabet`: The symbols each record's sequence may contain
+pub fn read_fasta<P: AsRef<Path>>(
+    fasta_file: P,
+    alphabet: Alphabet,
+) -> Result<Vec<Record>, FastaError> {
+    let path = fasta_file.as_ref();
+    let f = fs::File::open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => FastaError::FileNotFound(path.to_path_buf()),
+        _ => FastaError::Io(e),
+    })?;
+    parse_fasta(f, alphabet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+        }
+    }
+
+    #[test]
+    fn valid_records_parse() {
+        let records =
+            parse_fasta(&b">a desc\nACGU\nRYN\n>b\nacgt\n"[..], Alphabet::Nucleotide).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id(), "a");
+        assert_eq!(records[0].seq(), b"ACGURYN");
+        assert_eq!(records[1].seq(), b"acgt");
+    }
+
+    #[test]
+    fn missing_file_is_reported() {
+        match read_fasta("/nonexistent/input.fasta", Alphabet::Nucleotide) {
+            Err(FastaError::FileNotFound(path)) => {
+                assert_eq!(path, PathBuf::from("/nonexistent/input.fasta"))
+            }
+            other => panic!("expected FileNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_failures_are_io_errors() {
+        assert!(matches!(
+            parse_fasta(FailingReader, Alphabet::Nucleotide),
+            Err(FastaError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn headers_need_a_marker_and_an_id() {
+        assert!(matches!(
+            parse_fasta(&b"ACGT\n"[..], Alphabet::Nucleotide),
+            Err(FastaError::MalformedHeader { record: 1, .. })
+        ));
+        assert!(matches!(
+            parse_fasta(&b">a\nAC\n>\nGT\n"[..], Alphabet::Nucleotide),
+            Err(FastaError::MalformedHeader { record: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn empty_sequences_are_rejected() {
+        match parse_fasta(&b">a\n>b\nAC\n"[..], Alphabet::Nucleotide) {
+            Err(FastaError::EmptySequence { id }) => assert_eq!(id, "a"),
+            other => panic!("expected EmptySequence, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn symbols_are_checked_against_the_alphabet() {
+        assert_eq!(
+            parse_fasta(&b">a\nACXT\n"[..], Alphabet::Nucleotide)
+                .unwrap_err()
+                .to_string(),
+            "FASTA record 'a' has invalid Nucleotide symbol 'X' at position 3"
+        );
+        assert!(matches!(
+            parse_fasta(&b">p\nMK1\n"[..], Alphabet::Protein),
+            Err(FastaError::InvalidSymbol {
+                position: 3,
+                symbol: b'1',
+                alphabet: Alphabet::Protein,
+                ..
+            })
+        ));
+        assert!(parse_fasta(&b">p\nMKX*\n"[..], Alphabet::Protein).is_ok());
+    }
+
+    #[test]
+    fn bare_sequences_are_read_one_per_line() {
+        let records = parse_sequences("ACGT\n\n  GGTA \n", Alphabet::Nucleotide).unwrap();
+        let ids: Vec<&str> = records.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, ["1", "3"]);
+        assert_eq!(records[1].seq(), b"GGTA");
+        assert_eq!(
+            parse_sequences("\n>x\nAC\n", Alphabet::Nucleotide).unwrap()[0].id(),
+            "x"
+        );
+        assert!(matches!(
+            parse_sequences("ACGT\nACXT\n", Alphabet::Nucleotide),
+            Err(FastaError::InvalidSymbol { position: 3, .. })
+        ));
+    }
+}
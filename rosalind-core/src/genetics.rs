@@ -0,0 +1,393 @@
+//! Mendelian inheritance at one or more independently assorting loci,
+//!  backing IEV-, IPRB- and LIA-style probability questions. Probabilities
+//!  are exact rationals; see `rational::format_decimal` for output.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+
+use crate::problem::Error;
+
+/// How the two alleles at a locus combine into a phenotype
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dominance {
+    /// Uppercase alleles mask lowercase ones, so `AA` and `Aa` look alike.
+    ///  Two different alleles of the same case are both expressed, as with
+    ///  the A and B blood-group alleles.
+    Complete,
+    /// Both alleles are always expressed, so every genotype is distinct
+    Codominant,
+}
+
+/// The pair of alleles carried at each locus, e.g. `AaBb`. Alleles are
+///  single characters and each pair is stored dominant-first, so `aA` and
+///  `Aa` are the same genotype.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Genotype {
+    loci: Vec<(char, char)>,
+}
+
+impl Genotype {
+    /// Arguments:
+    /// * `loci`: The two alleles carried at each locus
+    pub fn new(loci: Vec<(char, char)>) -> Genotype {
+        Genotype {
+            loci: loci
+                .into_iter()
+                .map(|(a, b)| if b < a { (b, a) } else { (a, b) })
+                .collect(),
+        }
+    }
+
+    /// The allele pairs, one per locus
+    pub fn loci(&self) -> &[(char, char)] {
+        &self.loci
+    }
+
+    /// Whether both alleles at `locus` are the same
+    pub fn is_homozygous(&self, locus: usize) -> bool {
+        let (a, b) = self.loci[locus];
+        a == b
+    }
+
+    /// Whether the two alleles at `locus` differ
+    pub fn is_heterozygous(&self, locus: usize) -> bool {
+        !self.is_homozygous(locus)
+    }
+
+    /// The phenotype this genotype expresses
+    ///
+    /// Arguments:
+    /// * `dominance`: How the alleles combine at each locus; the last entry
+    ///   is reused for any further loci, so one entry covers every locus
+    pub fn phenotype(&self, dominance: &[Dominance]) -> Phenotype {
+        let traits = self
+            .loci
+            .iter()
+            .enumerate()
+            .map(|(i, &(a, b))| {
+                let mode = dominance
+                    .get(i)
+                    .or(dominance.last())
+                    .copied()
+                    .unwrap_or(Dominance::Complete);
+                match mode {
+                    Dominance::Codominant => format!("{}{}", a, b),
+                    Dominance::Complete if a == b => a.to_string(),
+                    Dominance::Complete if a.is_uppercase() == b.is_uppercase() => {
+                        format!("{}{}", a, b)
+                    }
+                    Dominance::Complete => a.to_string(),
+                }
+            })
+            .collect();
+        Phenotype(traits)
+    }
+}
+
+impl FromStr for Genotype {
+    type Err = Error;
+
+    /// Parse a genotype written as consecutive allele pairs, e.g. `AaBb`
+    fn from_str(s: &str) -> Result<Genotype, Error> {
+        let alleles: Vec<char> = s.trim().chars().collect();
+        if alleles.is_empty() || !alleles.len().is_multiple_of(2) {
+            return Err(
+                format!("genotype '{}' must be a non-empty list of allele pairs", s).into(),
+            );
+        }
+        Ok(Genotype::new(
+            alleles.chunks(2).map(|p| (p[0], p[1])).collect(),
+        ))
+    }
+}
+
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (a, b) in self.loci.iter() {
+            write!(f, "{}{}", a, b)?;
+        }
+        Ok(())
+    }
+}
+
+/// The expressed trait at each locus, e.g. `A` for a dominant phenotype
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Phenotype(pub Vec<String>);
+
+impl Phenotype {
+    /// Whether the trait at `locus` shows the dominant (uppercase) allele
+    pub fn is_dominant(&self, locus: usize) -> bool {
+        self.0[locus].chars().any(|c| c.is_uppercase())
+    }
+}
+
+impl fmt::Display for Phenotype {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+/// A probability distribution over outcomes, such as offspring genotypes
+pub type Distribution<T> = BTreeMap<T, BigRational>;
+
+fn ratio(numer: u64, denom: u64) -> BigRational {
+    BigRational::new(BigInt::from(numer), BigInt::from(denom))
+}
+
+/// A mating between two parents with the same number of loci
+#[derive(Clone, Debug)]
+pub struct Cross {
+    pub mother: Genotype,
+    pub father: Genotype,
+}
+
+impl Cross {
+    /// Arguments:
+    /// * `mother`: One parent's genotype
+    /// * `father`: The other parent's genotype
+    pub fn new(mother: Genotype, father: Genotype) -> Result<Cross, Error> {
+        if mother.loci.len() != father.loci.len() {
+            return Err(format!(
+                "cannot cross {} with {}: they have different numbers of loci",
+                mother, father
+            )
+            .into());
+        }
+        Ok(Cross { mother, father })
+    }
+
+    /// The distribution of offspring genotypes, with every locus assorting
+    ///  independently and each parent passing on either allele with equal
+    ///  probability
+    pub fn offspring(&self) -> Distribution<Genotype> {
+        let quarter = ratio(1, 4);
+        let mut partial: Distribution<Vec<(char, char)>> =
+            BTreeMap::from([(vec![], BigRational::one())]);
+        for (&(m1, m2), &(f1, f2)) in self.mother.loci.iter().zip(self.father.loci.iter()) {
+            let mut next: Distribution<Vec<(char, char)>> = BTreeMap::new();
+            for (loci, p) in partial {
+                for m in [m1, m2] {
+                    for f in [f1, f2] {
+                        let mut loci = loci.clone();
+                        loci.push((m, f));
+                        *next
+                            .entry(Genotype::new(loci).loci)
+                            .or_insert_with(BigRational::zero) += &p * &quarter;
+                    }
+                }
+            }
+            partial = next;
+        }
+        partial
+            .into_iter()
+            .map(|(loci, p)| (Genotype { loci }, p))
+            .collect()
+    }
+
+    /// The distribution of offspring phenotypes
+    ///
+    /// Arguments:
+    /// * `dominance`: How the alleles combine at each locus
+    pub fn phenotypes(&self, dominance: &[Dominance]) -> Distribution<Phenotype> {
+        let mut out: Distribution<Phenotype> = BTreeMap::new();
+        for (g, p) in self.offspring() {
+            *out.entry(g.phenotype(dominance))
+                .or_insert_with(BigRational::zero) += p;
+        }
+        out
+    }
+
+    /// The probability that a single offspring satisfies `predicate`
+    ///
+    /// Arguments:
+    /// * `predicate`: The condition on the offspring's genotype
+    pub fn probability<F: Fn(&Genotype) -> bool>(&self, predicate: F) -> BigRational {
+        self.offspring()
+            .into_iter()
+            .filter(|(g, _)| predicate(g))
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// The expected number of offspring satisfying `predicate` among
+    ///  `offspring` independent children
+    ///
+    /// Arguments:
+    /// * `offspring`: The number of children
+    /// * `predicate`: The condition on each child's genotype
+    pub fn expected_offspring<F: Fn(&Genotype) -> bool>(
+        &self,
+        offspring: u64,
+        predicate: F,
+    ) -> BigRational {
+        self.probability(predicate) * BigInt::from(offspring)
+    }
+}
+
+/// The genotype distribution of a descendant after `generations` rounds of
+///  mating with the same `partner` genotype each time
+///
+/// Arguments:
+/// * `start`: The founding individual's genotype
+/// * `partner`: The genotype every descendant mates with
+/// * `generations`: The number of generations to advance
+pub fn descendant_distribution(
+    start: &Genotype,
+    partner: &Genotype,
+    generations: usize,
+) -> Result<Distribution<Genotype>, Error> {
+    let mut current: Distribution<Genotype> = BTreeMap::from([(start.clone(), BigRational::one())]);
+    for _ in 0..generations {
+        let mut next: Distribution<Genotype> = BTreeMap::new();
+        for (g, p) in current {
+            for (child, q) in Cross::new(g, partner.clone())?.offspring() {
+                *next.entry(child).or_insert_with(BigRational::zero) += &p * q;
+            }
+        }
+        current = next;
+    }
+    Ok(current)
+}
+
+/// The probability that two organisms drawn at random from a population of
+///  `k` homozygous dominant, `m` heterozygous and `n` homozygous recessive
+///  individuals produce offspring with the dominant phenotype (Rosalind IPRB)
+///
+/// Arguments:
+/// * `k`: The number of `AA` individuals
+/// * `m`: The number of `Aa` individuals
+/// * `n`: The number of `aa` individuals
+pub fn random_mating_dominant_probability(k: u64, m: u64, n: u64) -> Result<BigRational, Error> {
+    let total = k + m + n;
+    if total < 2 {
+        return Err("random mating needs a population of at least two".into());
+    }
+    let groups = [("AA", k), ("Aa", m), ("aa", n)];
+    let mut probability = BigRational::zero();
+    for (i, &(g1, c1)) in groups.iter().enumerate() {
+        for (j, &(g2, c2)) in groups.iter().enumerate() {
+            let pairs = if i == j {
+                c1 * c1.saturating_sub(1)
+            } else {
+                c1 * c2
+            };
+            if pairs == 0 {
+                continue;
+            }
+            let pick = ratio(pairs, total * (total - 1));
+            let cross = Cross::new(g1.parse()?, g2.parse()?)?;
+            probability +=
+                pick * cross.probability(|g| g.phenotype(&[Dominance::Complete]).is_dominant(0));
+        }
+    }
+    Ok(probability)
+}
+
+/// The probability that at least `n` of the `2^k` organisms in generation
+///  `k` are `AaBb`, when Tom (`AaBb`) founds generation 0 and every organism
+///  has two children with an `AaBb` partner (Rosalind LIA)
+///
+/// Arguments:
+/// * `k`: The generation to examine, at most 7
+/// * `n`: The minimum number of `AaBb` organisms
+pub fn independent_alleles_probability(k: u32, n: u64) -> Result<BigRational, Error> {
+    if k > 7 {
+        return Err(format!("generation {} is too large; k must be at most 7", k).into());
+    }
+    let tom: Genotype = "AaBb".parse()?;
+    let dist = descendant_distribution(&tom, &tom, k as usize)?;
+    let p = dist.get(&tom).cloned().unwrap_or_else(BigRational::zero);
+    let q = BigRational::one() - &p;
+    let total = 1u64 << k;
+    if n > total {
+        return Ok(BigRational::zero());
+    }
+
+    // P(X >= n) = 1 - P(X < n) for X ~ Binomial(2^k, p), building each term
+    //  of the lower tail from the one before it
+    let mut term = (0..total).fold(BigRational::one(), |acc, _| acc * &q);
+    let mut below = BigRational::zero();
+    for i in 0..n {
+        below += &term;
+        if q.is_zero() {
+            break;
+        }
+        term = term * ratio(total - i, i + 1) * &p / &q;
+    }
+    Ok(BigRational::one() - below)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rational::format_decimal;
+
+    fn cross(mother: &str, father: &str) -> Cross {
+        Cross::new(mother.parse().unwrap(), father.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn rosalind_samples() {
+        let iprb = random_mating_dominant_probability(2, 2, 2).unwrap();
+        assert_eq!(iprb, ratio(47, 60));
+        assert_eq!(format_decimal(&iprb, Some(5)), "0.78333");
+        let lia = independent_alleles_probability(2, 1).unwrap();
+        assert_eq!(format_decimal(&lia, Some(3)), "0.684");
+        assert!(independent_alleles_probability(1, 3).unwrap().is_zero());
+    }
+
+    #[test]
+    fn dihybrid_cross_splits_9_3_3_1() {
+        let phenotypes = cross("AaBb", "AaBb").phenotypes(&[Dominance::Complete]);
+        let split: Vec<(String, BigRational)> = phenotypes
+            .into_iter()
+            .map(|(ph, p)| (ph.to_string(), p))
+            .collect();
+        assert_eq!(
+            split,
+            vec![
+                ("A B".to_string(), ratio(9, 16)),
+                ("A b".to_string(), ratio(3, 16)),
+                ("a B".to_string(), ratio(3, 16)),
+                ("a b".to_string(), ratio(1, 16)),
+            ]
+        );
+        assert_eq!(cross("AaBb", "AaBb").offspring().len(), 9);
+    }
+
+    #[test]
+    fn codominant_alleles_are_all_expressed() {
+        let c = cross("Aa", "Aa");
+        let complete = c.phenotypes(&[Dominance::Complete]);
+        assert_eq!(complete.len(), 2);
+        let codominant = c.phenotypes(&[Dominance::Codominant]);
+        let expected: Distribution<Phenotype> = [("AA", 1, 4), ("Aa", 1, 2), ("aa", 1, 4)]
+            .into_iter()
+            .map(|(t, n, d)| (Phenotype(vec![t.to_string()]), ratio(n, d)))
+            .collect();
+        assert_eq!(codominant, expected);
+        // blood groups: A and B are both expressed even under complete dominance
+        let blood = cross("Ao", "Bo").phenotypes(&[Dominance::Complete]);
+        let expected: Distribution<Phenotype> = ["A", "AB", "B", "o"]
+            .into_iter()
+            .map(|t| (Phenotype(vec![t.to_string()]), ratio(1, 4)))
+            .collect();
+        assert_eq!(blood, expected);
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        assert!("".parse::<Genotype>().is_err());
+        assert!("AaB".parse::<Genotype>().is_err());
+        assert!(Cross::new("Aa".parse().unwrap(), "AaBb".parse().unwrap()).is_err());
+        assert!(random_mating_dominant_probability(1, 0, 0).is_err());
+        assert!(independent_alleles_probability(7, 1).is_ok());
+        assert!(independent_alleles_probability(8, 1).is_err());
+        assert!(independent_alleles_probability(64, 1).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+//! Shared building blocks for the Rosalind problem binaries
+
+pub mod fasta;
+pub mod genetics;
+pub mod matrix;
+pub mod modular;
+pub mod options;
+pub mod population;
+pub mod problem;
+pub mod rational;
+pub mod recurrence;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use options::Options;
+pub use problem::{Error, Problem, Solver};
@@ -0,0 +1,133 @@
+//! Dense big-integer matrices, with exponentiation by repeated squaring
+//!  for advancing linear models many steps at once
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::modular;
+
+/// A dense square matrix of big integers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    size: usize,
+    data: Vec<BigUint>,
+}
+
+impl Matrix {
+    /// The `size` x `size` matrix of zeroes
+    pub fn zeros(size: usize) -> Matrix {
+        Matrix {
+            size,
+            data: vec![BigUint::zero(); size * size],
+        }
+    }
+
+    /// The `size` x `size` identity matrix
+    pub fn identity(size: usize) -> Matrix {
+        let mut m = Matrix::zeros(size);
+        for i in 0..size {
+            m[(i, i)] = BigUint::one();
+        }
+        m
+    }
+
+    /// Build a matrix from its rows, which must all have the same length as
+    ///  there are rows
+    pub fn from_rows(rows: Vec<Vec<BigUint>>) -> Matrix {
+        let size = rows.len();
+        assert!(
+            rows.iter().all(|r| r.len() == size),
+            "matrix rows must form a square"
+        );
+        Matrix {
+            size,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The number of rows (and columns)
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Multiply two matrices of the same size
+    ///
+    /// Arguments:
+    /// * `rhs`: The right-hand operand
+    /// * `modulo`: Reduce every entry modulo this value, if given
+    pub fn mul(&self, rhs: &Matrix, modulo: Option<&BigUint>) -> Matrix {
+        assert_eq!(self.size, rhs.size, "matrix sizes must match");
+        let n = self.size;
+        let mut out = Matrix::zeros(n);
+        for i in 0..n {
+            for k in 0..n {
+                let a = &self[(i, k)];
+                if a.is_zero() {
+                    continue;
+                }
+                for j in 0..n {
+                    out[(i, j)] += a * &rhs[(k, j)];
+                }
+            }
+            for j in 0..n {
+                let v = std::mem::take(&mut out[(i, j)]);
+                out[(i, j)] = modular::reduce(v, modulo);
+            }
+        }
+        out
+    }
+
+    /// Multiply this matrix by a column vector
+    ///
+    /// Arguments:
+    /// * `v`: The vector, with one entry per column
+    /// * `modulo`: Reduce every entry modulo this value, if given
+    pub fn mul_vec(&self, v: &[BigUint], modulo: Option<&BigUint>) -> Vec<BigUint> {
+        assert_eq!(self.size, v.len(), "vector length must match matrix size");
+        (0..self.size)
+            .map(|i| {
+                let sum = (0..self.size)
+                    .map(|j| &self[(i, j)] * &v[j])
+                    .sum::<BigUint>();
+                modular::reduce(sum, modulo)
+            })
+            .collect()
+    }
+
+    /// Raise this matrix to the power `exp` by repeated squaring
+    ///
+    /// Arguments:
+    /// * `exp`: The exponent
+    /// * `modulo`: Reduce every entry modulo this value, if given
+    pub fn pow(&self, mut exp: u64, modulo: Option<&BigUint>) -> Matrix {
+        let mut result = Matrix::identity(self.size);
+        let mut base = self.clone();
+        for v in result.data.iter_mut() {
+            *v = modular::reduce(std::mem::take(v), modulo);
+        }
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base, modulo);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul(&base, modulo);
+            }
+        }
+        result
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix {
+    type Output = BigUint;
+
+    fn index(&self, (row, col): (usize, usize)) -> &BigUint {
+        &self.data[row * self.size + col]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut BigUint {
+        &mut self.data[row * self.size + col]
+    }
+}
@@ -0,0 +1,30 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::options::Options;
+use crate::problem::Error;
+
+/// Reduce `value` modulo `modulo`, or return it unchanged if no modulus
+///  was requested
+///
+/// Arguments:
+/// * `value`: The value to reduce
+/// * `modulo`: The optional modulus
+pub fn reduce(value: BigUint, modulo: Option<&BigUint>) -> BigUint {
+    match modulo {
+        Some(m) => value % m,
+        None => value,
+    }
+}
+
+/// Take the `--modulo M` option shared by solvers with exact integer answers
+///
+/// Arguments:
+/// * `options`: The command-line options to take `--modulo` from
+pub fn take_modulo(options: &mut Options) -> Result<Option<BigUint>, Error> {
+    let modulo: Option<BigUint> = options.take("modulo")?;
+    if modulo.as_ref().is_some_and(|m| m.is_zero()) {
+        return Err("--modulo must be greater than zero".into());
+    }
+    Ok(modulo)
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::problem::Error;
+
+/// Command-line options passed after the dataset path, in `--name value`
+///  or bare `--flag` form. Solvers take the options they understand while
+///  being configured, and anything left over is reported as unrecognised.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Each occurrence of each option, with `None` for a bare `--name`
+    values: HashMap<String, Vec<Option<String>>>,
+}
+
+impl Options {
+    /// Parse a list of `--name value` / `--flag` arguments
+    ///
+    /// Arguments:
+    /// * `args`: The arguments to parse, without the program name or dataset path
+    pub fn parse(args: &[String]) -> Result<Options, Error> {
+        let mut values: HashMap<String, Vec<Option<String>>> = HashMap::new();
+        let mut i = 0;
+        while i < args.len() {
+            let name = args[i]
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument '{}'", args[i]))?;
+            let entry = values.entry(name.to_string()).or_default();
+            match args.get(i + 1) {
+                Some(value) if !value.starts_with("--") => {
+                    entry.push(Some(value.clone()));
+                    i += 2;
+                }
+                _ => {
+                    entry.push(None);
+                    i += 1;
+                }
+            }
+        }
+        Ok(Options { values })
+    }
+
+    /// Remove and parse the last value given for `--name`, if any
+    pub fn take<T>(&mut self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        Ok(self.take_all(name)?.pop())
+    }
+
+    /// Remove and parse every value given for a repeatable `--name`. A
+    ///  bare `--name` with no value is an error.
+    pub fn take_all<T>(&mut self, name: &str) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let values = self.values.remove(name).unwrap_or_default();
+        values
+            .iter()
+            .map(|v| {
+                let v = v
+                    .as_ref()
+                    .ok_or_else(|| format!("--{} requires a value", name))?;
+                v.parse::<T>()
+                    .map_err(|e| format!("invalid value '{}' for --{}: {}", v, name, e).into())
+            })
+            .collect()
+    }
+
+    /// Remove a bare `--name` flag, returning whether it was present
+    pub fn take_flag(&mut self, name: &str) -> Result<bool, Error> {
+        match self.values.remove(name) {
+            Some(values) if values.iter().any(Option::is_some) => {
+                Err(format!("--{} does not take a value", name).into())
+            }
+            Some(_) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// Fail if any options were not taken by the solver they were passed to
+    pub fn finish(self) -> Result<(), Error> {
+        let mut names: Vec<&String> = self.values.keys().collect();
+        names.sort();
+        match names.first() {
+            Some(name) => Err(format!("unrecognised option --{}", name).into()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(args: &[&str]) -> Options {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        Options::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn values_and_flags_are_taken() {
+        let mut o = options(&["--modulo", "7", "--all", "--k", "3"]);
+        assert_eq!(o.take::<u64>("modulo").unwrap(), Some(7));
+        assert!(o.take_flag("all").unwrap());
+        assert!(!o.take_flag("count").unwrap());
+        assert_eq!(o.take::<usize>("k").unwrap(), Some(3));
+        assert_eq!(o.take::<usize>("missing").unwrap(), None);
+        assert!(o.finish().is_ok());
+    }
+
+    #[test]
+    fn repeated_values_are_kept_in_order() {
+        let mut o = options(&["--motif", "A", "--motif", "B"]);
+        assert_eq!(o.take_all::<String>("motif").unwrap(), vec!["A", "B"]);
+        let mut o = options(&["--k", "1", "--k", "2"]);
+        assert_eq!(o.take::<usize>("k").unwrap(), Some(2));
+    }
+
+    #[test]
+    fn a_value_option_without_a_value_is_an_error() {
+        let mut o = options(&["--modulo"]);
+        assert_eq!(
+            o.take::<u64>("modulo").unwrap_err().to_string(),
+            "--modulo requires a value"
+        );
+        let mut o = options(&["--modulo", "--all"]);
+        assert!(o.take::<u64>("modulo").is_err());
+        let mut o = options(&["--motif", "A", "--motif"]);
+        assert!(o.take_all::<String>("motif").is_err());
+    }
+
+    #[test]
+    fn bad_input_is_reported() {
+        let mut o = options(&["--modulo", "seven"]);
+        assert_eq!(
+            o.take::<u64>("modulo").unwrap_err().to_string(),
+            "invalid value 'seven' for --modulo: invalid digit found in string"
+        );
+        let mut o = options(&["--all", "yes"]);
+        assert!(o.take_flag("all").is_err());
+        assert_eq!(
+            options(&["--zeta", "--alpha"])
+                .finish()
+                .unwrap_err()
+                .to_string(),
+            "unrecognised option --alpha"
+        );
+        assert!(Options::parse(&["stray".to_string()]).is_err());
+    }
+}
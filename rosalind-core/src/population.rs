@@ -0,0 +1,235 @@
+//! Age-structured population models in the style of a Leslie matrix, of
+//!  which the FIB and FIBD rabbit problems are two special cases
+
+use std::ops::{Add, Mul};
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::matrix::Matrix;
+use crate::modular;
+use crate::problem::Error;
+
+/// A population split into age classes `0..n`, where each generation:
+///  * every individual of age `a` produces `fecundity[a]` newborns, and
+///  * a `survival[a]` share of age `a` moves on to age `a+1`.
+///
+/// Survivors of the oldest class stay in it, so a non-zero final survival
+///  models an immortal adult class while zero models a fixed lifespan.
+#[derive(Clone, Debug)]
+pub struct LeslieModel<T> {
+    fecundity: Vec<T>,
+    survival: Vec<T>,
+    initial: Vec<T>,
+}
+
+impl<T> LeslieModel<T>
+where
+    T: Clone + Zero + One + Add<Output = T> + Mul<Output = T>,
+{
+    /// Arguments:
+    /// * `fecundity`: Newborns per individual, for each age class
+    /// * `survival`: Share of each age class reaching the next one
+    /// * `initial`: The starting population, or a single newborn if `None`
+    pub fn new(
+        fecundity: Vec<T>,
+        survival: Vec<T>,
+        initial: Option<Vec<T>>,
+    ) -> Result<LeslieModel<T>, Error> {
+        if fecundity.is_empty() {
+            return Err("a population model needs at least one age class".into());
+        }
+        if survival.len() != fecundity.len() {
+            return Err(format!(
+                "expected {} survival rates, one per age class, found {}",
+                fecundity.len(),
+                survival.len()
+            )
+            .into());
+        }
+        let initial = match initial {
+            Some(v) if v.len() != fecundity.len() => {
+                return Err(format!(
+                    "expected {} starting counts, one per age class, found {}",
+                    fecundity.len(),
+                    v.len()
+                )
+                .into())
+            }
+            Some(v) => v,
+            None => {
+                let mut v = vec![T::zero(); fecundity.len()];
+                v[0] = T::one();
+                v
+            }
+        };
+        Ok(LeslieModel {
+            fecundity,
+            survival,
+            initial,
+        })
+    }
+
+    /// A population that breeds from `maturation_age` onwards, producing
+    ///  `litter_size` newborns per individual each generation, and either
+    ///  lives forever or dies after `lifespan` generations. Starts from a
+    ///  single newborn.
+    ///
+    /// Arguments:
+    /// * `maturation_age`: The first age at which individuals breed
+    /// * `litter_size`: Newborns per mature individual per generation
+    /// * `lifespan`: The number of generations each individual lives, if finite
+    pub fn with_maturation(
+        maturation_age: usize,
+        litter_size: T,
+        lifespan: Option<usize>,
+    ) -> Result<LeslieModel<T>, Error> {
+        let classes = match lifespan {
+            Some(0) => return Err("lifespan must be at least one generation".into()),
+            Some(m) => m,
+            None => maturation_age + 1,
+        };
+        let fecundity = (0..classes)
+            .map(|a| {
+                if a >= maturation_age {
+                    litter_size.clone()
+                } else {
+                    T::zero()
+                }
+            })
+            .collect();
+        let mut survival = vec![T::one(); classes];
+        if lifespan.is_some() {
+            survival[classes - 1] = T::zero();
+        }
+        LeslieModel::new(fecundity, survival, None)
+    }
+
+    /// Immortal rabbits that mature after one month and produce `k` pairs
+    ///  per month (Rosalind FIB)
+    pub fn fib(k: T) -> LeslieModel<T> {
+        LeslieModel::with_maturation(1, k, None).unwrap()
+    }
+
+    /// Rabbits that mature after one month, produce one pair per month and
+    ///  die after `m` months (Rosalind FIBD)
+    pub fn fibd(m: usize) -> Result<LeslieModel<T>, Error> {
+        LeslieModel::with_maturation(1, T::one(), Some(m))
+    }
+
+    /// The number of age classes
+    pub fn age_classes(&self) -> usize {
+        self.fecundity.len()
+    }
+
+    /// Advance an age distribution by one generation
+    ///
+    /// Arguments:
+    /// * `ages`: The population of each age class
+    pub fn step(&self, ages: &[T]) -> Vec<T> {
+        let n = self.age_classes();
+        let mut next = vec![T::zero(); n];
+        next[0] = ages
+            .iter()
+            .zip(self.fecundity.iter())
+            .fold(T::zero(), |acc, (p, f)| acc + p.clone() * f.clone());
+        for (a, (p, s)) in ages.iter().zip(self.survival.iter()).enumerate() {
+            let survivors = p.clone() * s.clone();
+            let to = (a + 1).min(n - 1);
+            next[to] = next[to].clone() + survivors;
+        }
+        next
+    }
+
+    /// The age distribution of every generation from the start up to and
+    ///  including `generations`
+    ///
+    /// Arguments:
+    /// * `generations`: The number of generations to simulate
+    pub fn simulate(&self, generations: usize) -> Vec<Vec<T>> {
+        let mut history = vec![self.initial.clone()];
+        for _ in 0..generations {
+            let next = self.step(history.last().unwrap());
+            history.push(next);
+        }
+        history
+    }
+}
+
+impl LeslieModel<BigUint> {
+    /// The Leslie matrix mapping one generation's age distribution to the next
+    pub fn matrix(&self) -> Matrix {
+        let n = self.age_classes();
+        let mut m = Matrix::zeros(n);
+        for (a, f) in self.fecundity.iter().enumerate() {
+            m[(0, a)] = f.clone();
+        }
+        for (a, s) in self.survival.iter().enumerate() {
+            m[((a + 1).min(n - 1), a)] += s;
+        }
+        m
+    }
+
+    /// The age distribution after `generation` generations, computed by
+    ///  matrix exponentiation so that very distant generations stay cheap
+    ///
+    /// Arguments:
+    /// * `generation`: The number of generations to advance
+    /// * `modulo`: Reduce every count modulo this value, if given
+    pub fn at(&self, generation: u64, modulo: Option<&BigUint>) -> Vec<BigUint> {
+        self.matrix()
+            .pow(generation, modulo)
+            .mul_vec(&self.initial, modulo)
+    }
+
+    /// The total population after `generation` generations
+    ///
+    /// Arguments:
+    /// * `generation`: The number of generations to advance
+    /// * `modulo`: Reduce the total modulo this value, if given
+    pub fn total_at(&self, generation: u64, modulo: Option<&BigUint>) -> BigUint {
+        modular::reduce(self.at(generation, modulo).into_iter().sum(), modulo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_match_the_rosalind_samples() {
+        // FIB 5 3 and FIBD 6 3, counted from the first month
+        assert_eq!(
+            LeslieModel::fib(BigUint::from(3u8)).total_at(4, None),
+            BigUint::from(19u8)
+        );
+        assert_eq!(
+            LeslieModel::<BigUint>::fibd(3).unwrap().total_at(5, None),
+            BigUint::from(4u8)
+        );
+    }
+
+    #[test]
+    fn matrix_powers_agree_with_simulation() {
+        let model = LeslieModel::new(
+            vec![0u64, 2, 1].into_iter().map(BigUint::from).collect(),
+            vec![1u64, 1, 0].into_iter().map(BigUint::from).collect(),
+            None,
+        )
+        .unwrap();
+        for (g, ages) in model.simulate(20).into_iter().enumerate() {
+            assert_eq!(model.at(g as u64, None), ages);
+        }
+        let m = BigUint::from(1000u32);
+        let total: BigUint = model.simulate(20).pop().unwrap().into_iter().sum();
+        assert_eq!(model.total_at(20, Some(&m)), total % &m);
+    }
+
+    #[test]
+    fn inconsistent_models_are_rejected() {
+        assert!(LeslieModel::<u64>::new(vec![], vec![], None).is_err());
+        assert!(LeslieModel::new(vec![0u64, 1], vec![1], None).is_err());
+        assert!(LeslieModel::new(vec![0u64, 1], vec![1, 1], Some(vec![1])).is_err());
+        assert!(LeslieModel::<u64>::fibd(0).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::env;
+use std::fs;
+
+use crate::options::Options;
+
+/// The error type shared by every stage of every solver
+pub type Error = Box<dyn std::error::Error>;
+
+/// A Rosalind problem, split into the stages every solver goes through:
+///  parse the dataset, solve it, and format the answer for submission
+pub trait Problem {
+    type Input;
+    type Output;
+
+    /// The Rosalind problem ID, e.g. `"DNA"`
+    fn id(&self) -> &'static str;
+
+    /// Take any solver-specific command-line options, e.g. `--modulo`
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        let _ = options;
+        Ok(())
+    }
+
+    /// Turn the raw contents of a dataset file into the solver's input
+    fn parse(&self, input: &str) -> Result<Self::Input, Error>;
+
+    /// Compute the answer for a parsed dataset
+    fn solve(&self, input: Self::Input) -> Result<Self::Output, Error>;
+
+    /// Render an answer in the format Rosalind expects, without a trailing newline
+    fn format(&self, output: &Self::Output) -> String;
+
+    /// Write any files the solver was asked to produce alongside the answer,
+    ///  e.g. a `--layout` report. Does nothing by default.
+    fn export(&self, output: &Self::Output) -> Result<(), Error> {
+        let _ = output;
+        Ok(())
+    }
+
+    /// Whether `actual` is an acceptable answer for `input`, given a known-good
+    ///  answer `expected`. Defaults to an exact match ignoring surrounding
+    ///  whitespace; problems with floating-point output or more than one
+    ///  valid answer override this.
+    fn check(&self, input: &Self::Input, expected: &str, actual: &str) -> bool {
+        let _ = input;
+        expected.trim() == actual.trim()
+    }
+}
+
+/// Object-safe view of a `Problem`, so solvers with different input and
+///  output types can be registered side-by-side
+pub trait Solver {
+    /// The Rosalind problem ID, e.g. `"DNA"`
+    fn id(&self) -> &'static str;
+
+    /// Take any solver-specific command-line options, e.g. `--modulo`
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error>;
+
+    /// Parse, solve and format a dataset in one go, writing any files the
+    ///  solver exports along the way
+    fn run(&self, input: &str) -> Result<String, Error>;
+
+    /// Parse a dataset and check a formatted answer against a known-good one
+    fn check(&self, input: &str, expected: &str, actual: &str) -> Result<bool, Error>;
+}
+
+impl<P: Problem> Solver for P {
+    fn id(&self) -> &'static str {
+        Problem::id(self)
+    }
+
+    fn configure(&mut self, options: &mut Options) -> Result<(), Error> {
+        Problem::configure(self, options)
+    }
+
+    fn run(&self, input: &str) -> Result<String, Error> {
+        let parsed = self.parse(input)?;
+        let output = self.solve(parsed)?;
+        self.export(&output)?;
+        Ok(self.format(&output))
+    }
+
+    fn check(&self, input: &str, expected: &str, actual: &str) -> Result<bool, Error> {
+        let parsed = self.parse(input)?;
+        Ok(Problem::check(self, &parsed, expected, actual))
+    }
+}
+
+/// Run a solver against the dataset file named by the first command-line
+///  argument and print the answer, for use as a problem binary's `main`.
+///  Any further arguments are passed to the solver as `Options`.
+///
+/// Arguments:
+/// * `solver`: The solver to run
+pub fn run_main(solver: &mut dyn Solver) -> Result<(), Error> {
+    let args: Vec<String> = env::args().collect();
+    let filepath = args
+        .get(1)
+        .ok_or_else(|| format!("usage: {} <input> [options]", solver.id()))?;
+
+    let mut options = Options::parse(&args[2..])?;
+    solver.configure(&mut options)?;
+    options.finish()?;
+
+    let input = fs::read_to_string(filepath)?;
+    println!("{}", solver.run(&input)?);
+
+    Ok(())
+}
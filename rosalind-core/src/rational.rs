@@ -0,0 +1,96 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use crate::options::Options;
+use crate::problem::Error;
+
+/// Decimal places printed for answers that have no finite decimal expansion,
+///  when no `--digits` option was given
+pub const DEFAULT_DIGITS: usize = 6;
+
+/// The number of decimal places needed to print `value` exactly, or `None`
+///  if its expansion never terminates
+fn terminating_digits(value: &BigRational) -> Option<usize> {
+    let two = BigInt::from(2);
+    let five = BigInt::from(5);
+    let mut denom = value.denom().abs();
+    let (mut twos, mut fives) = (0, 0);
+    while denom.is_multiple_of(&two) {
+        denom /= &two;
+        twos += 1;
+    }
+    while denom.is_multiple_of(&five) {
+        denom /= &five;
+        fives += 1;
+    }
+    if denom.is_one() {
+        Some(twos.max(fives))
+    } else {
+        None
+    }
+}
+
+/// Render an exact rational as a decimal string, rounding half away from
+///  zero
+///
+/// Arguments:
+/// * `value`: The value to print
+/// * `digits`: The number of decimal places; if `None`, values with a finite
+///   expansion are printed exactly and all others use `DEFAULT_DIGITS`
+pub fn format_decimal(value: &BigRational, digits: Option<usize>) -> String {
+    let digits = digits
+        .or_else(|| terminating_digits(value))
+        .unwrap_or(DEFAULT_DIGITS);
+
+    let scale = num_traits::pow(BigInt::from(10), digits);
+    let scaled = value.abs() * BigRational::from_integer(scale.clone());
+    let rounded = (scaled + BigRational::new(BigInt::one(), BigInt::from(2))).floor();
+    let (int_part, frac_part) = rounded.to_integer().div_rem(&scale);
+
+    let sign = if value.is_negative() && !rounded.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    if digits == 0 {
+        return format!("{}{}", sign, int_part);
+    }
+    let frac = frac_part.to_str_radix(10);
+    format!(
+        "{}{}.{}{}",
+        sign,
+        int_part,
+        "0".repeat(digits - frac.len()),
+        frac
+    )
+}
+
+/// Parse a decimal string such as `-3.25` into an exact rational
+///
+/// Arguments:
+/// * `s`: The decimal to parse
+pub fn parse_decimal(s: &str) -> Result<BigRational, Error> {
+    let s = s.trim();
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(rest) => (Sign::Minus, rest),
+        None => (Sign::Plus, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    let all: String = format!("{}{}", int_part, frac_part);
+    if all.is_empty() || !all.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' is not a decimal number", s).into());
+    }
+    let numer = BigInt::from_biguint(sign, all.parse()?);
+    let denom = num_traits::pow(BigInt::from(10), frac_part.len());
+    Ok(BigRational::new(numer, denom))
+}
+
+/// Take the `--digits N` option shared by solvers with probability answers
+///
+/// Arguments:
+/// * `options`: The command-line options to take `--digits` from
+pub fn take_digits(options: &mut Options) -> Result<Option<usize>, Error> {
+    options.take("digits")
+}
@@ -0,0 +1,137 @@
+//! Linear recurrences with constant coefficients, evaluated in O(d³ log n)
+//!  by raising their companion matrix to the n-th power
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::matrix::Matrix;
+use crate::modular;
+
+/// A sequence defined by `a(n) = c[0]·a(n-1) + c[1]·a(n-2) + ... + c[d-1]·a(n-d)`
+///  together with its first `d` terms `a(0), ..., a(d-1)`
+#[derive(Clone, Debug)]
+pub struct LinearRecurrence {
+    coefficients: Vec<BigUint>,
+    initial: Vec<BigUint>,
+}
+
+impl LinearRecurrence {
+    /// Arguments:
+    /// * `coefficients`: The weights of `a(n-1)` through `a(n-d)`
+    /// * `initial`: The first `d` terms, starting from `a(0)`
+    pub fn new(coefficients: Vec<BigUint>, initial: Vec<BigUint>) -> LinearRecurrence {
+        assert!(
+            !coefficients.is_empty(),
+            "a recurrence needs an order of at least 1"
+        );
+        assert_eq!(
+            coefficients.len(),
+            initial.len(),
+            "a recurrence of order d needs d initial terms"
+        );
+        LinearRecurrence {
+            coefficients,
+            initial,
+        }
+    }
+
+    /// The order `d` of the recurrence
+    pub fn order(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// The companion matrix `C`, which maps the state vector
+    ///  `[a(n), a(n-1), ..., a(n-d+1)]` to `[a(n+1), a(n), ..., a(n-d+2)]`
+    pub fn companion(&self) -> Matrix {
+        let d = self.order();
+        let mut c = Matrix::zeros(d);
+        for (j, coef) in self.coefficients.iter().enumerate() {
+            c[(0, j)] = coef.clone();
+        }
+        for i in 1..d {
+            c[(i, i - 1)] = BigUint::one();
+        }
+        c
+    }
+
+    /// The state vector `[a(n), a(n-1), ..., a(n-d+1)]`, for `n >= d-1`
+    ///
+    /// Arguments:
+    /// * `n`: The index of the newest term in the state
+    /// * `modulo`: Reduce every term modulo this value, if given
+    pub fn state(&self, n: u64, modulo: Option<&BigUint>) -> Vec<BigUint> {
+        let d = self.order() as u64;
+        assert!(n + 1 >= d, "the state at n needs n >= d-1");
+        let start: Vec<BigUint> = self
+            .initial
+            .iter()
+            .rev()
+            .map(|v| modular::reduce(v.clone(), modulo))
+            .collect();
+        self.companion()
+            .pow(n + 1 - d, modulo)
+            .mul_vec(&start, modulo)
+    }
+
+    /// The term `a(n)`
+    ///
+    /// Arguments:
+    /// * `n`: The index of the term
+    /// * `modulo`: Reduce the term modulo this value, if given
+    pub fn term(&self, n: u64, modulo: Option<&BigUint>) -> BigUint {
+        if n < self.order() as u64 {
+            return modular::reduce(self.initial[n as usize].clone(), modulo);
+        }
+        self.state(n, modulo).swap_remove(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci() -> LinearRecurrence {
+        let one = BigUint::one();
+        LinearRecurrence::new(vec![one.clone(), one.clone()], vec![BigUint::ZERO, one])
+    }
+
+    #[test]
+    fn terms_are_exact_without_a_modulus() {
+        let fib = fibonacci();
+        let first: Vec<BigUint> = (0..10).map(|n| fib.term(n, None)).collect();
+        let expected: Vec<BigUint> = [0u8, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        assert_eq!(first, expected);
+        assert_eq!(fib.term(100, None).to_string(), "354224848179261915075");
+    }
+
+    #[test]
+    fn terms_are_reduced_by_a_modulus() {
+        let m = BigUint::from(1_000_000_007u32);
+        let fib = fibonacci();
+        assert_eq!(fib.term(100, Some(&m)), fib.term(100, None) % &m);
+        assert_eq!(fib.term(1, Some(&BigUint::one())), BigUint::ZERO);
+        // F(10^18) mod 10^9+7
+        assert_eq!(
+            fib.term(1_000_000_000_000_000_000, Some(&m)).to_string(),
+            "209783453"
+        );
+    }
+
+    #[test]
+    fn the_state_holds_the_latest_terms() {
+        let tribonacci = LinearRecurrence::new(
+            vec![BigUint::one(); 3],
+            vec![BigUint::ZERO, BigUint::ZERO, BigUint::one()],
+        );
+        assert_eq!(tribonacci.order(), 3);
+        let state: Vec<u32> = tribonacci
+            .state(7, None)
+            .iter()
+            .map(|v| u32::try_from(v).unwrap())
+            .collect();
+        assert_eq!(state, vec![13, 7, 4]);
+    }
+}
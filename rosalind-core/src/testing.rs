@@ -0,0 +1,51 @@
+//! Helpers shared by the problem crates' unit tests. Enabled by the
+//!  `testing` feature, which each crate turns on for its dev-dependency.
+
+use crate::options::Options;
+use crate::problem::{Error, Problem};
+
+/// A default solver configured from command-line style `args`, as
+///  `run_main` would, so unrecognised options are an error too
+///
+/// Arguments:
+/// * `args`: The options, e.g. `["--modulo", "7"]`
+pub fn configured<P: Problem + Default>(args: &[&str]) -> Result<P, Error> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let mut options = Options::parse(&args)?;
+    let mut solver = P::default();
+    solver.configure(&mut options)?;
+    options.finish()?;
+    Ok(solver)
+}
+
+/// A seeded xorshift generator, so randomised tests draw the same cases
+///  on every run without depending on an RNG crate
+#[derive(Clone, Debug)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Arguments:
+    /// * `seed`: The starting state, which must be non-zero
+    pub fn new(seed: u64) -> XorShift {
+        assert_ne!(seed, 0, "a xorshift generator needs a non-zero seed");
+        XorShift { state: seed }
+    }
+
+    /// The next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A random value in `0..n`
+    ///
+    /// Arguments:
+    /// * `n`: The exclusive upper bound, which must be non-zero
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
@@ -0,0 +1,36 @@
+//! Registry of every Rosalind solver in the workspace
+
+use rosalind_core::Solver;
+
+/// Every registered solver, in alphabetical order of problem ID. New problems
+///  only need to implement `rosalind_core::Problem` and be added here.
+pub fn solvers() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(dbru::Dbru),
+        Box::new(dna::Dna),
+        Box::new(fib::Fib::default()),
+        Box::new(fibd::Fibd::default()),
+        Box::new(gasm::Gasm),
+        Box::new(grph::Grph::default()),
+        Box::new(iev::Iev::default()),
+        Box::new(lexf::Lexf::default()),
+        Box::new(lgis::Lgis::default()),
+        Box::new(long::Long::default()),
+        Box::new(mprt::Mprt::default()),
+        Box::new(pcov::Pcov),
+        Box::new(perm::Perm),
+        Box::new(pmch::Pmch::default()),
+        Box::new(sign::Sign),
+        Box::new(splc::Splc::default()),
+    ]
+}
+
+/// Look up a solver by its Rosalind problem ID, ignoring case
+///
+/// Arguments:
+/// * `id`: The problem ID to look up, e.g. `"LGIS"`
+pub fn find_solver(id: &str) -> Option<Box<dyn Solver>> {
+    solvers()
+        .into_iter()
+        .find(|s| s.id().eq_ignore_ascii_case(id))
+}
@@ -0,0 +1,51 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use rosalind::{find_solver, solvers};
+use rosalind_core::{Error, Options, Solver};
+
+fn usage() -> String {
+    let ids = solvers()
+        .iter()
+        .map(|s| s.id())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    format!(
+        "usage: rosalind <PROBLEM> <input> [options]\nproblems: {}",
+        ids
+    )
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        eprintln!("{}", usage());
+        process::exit(2);
+    }
+
+    let mut solver = match find_solver(&args[1]) {
+        Some(s) => s,
+        None => {
+            eprintln!("unknown problem '{}'\n{}", args[1], usage());
+            process::exit(2);
+        }
+    };
+
+    match run(solver.as_mut(), &args[2], &args[3..]) {
+        Ok(answer) => println!("{}", answer),
+        Err(e) => {
+            eprintln!("{}: {}", solver.id(), e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run(solver: &mut dyn Solver, filepath: &str, args: &[String]) -> Result<String, Error> {
+    let mut options = Options::parse(args)?;
+    solver.configure(&mut options)?;
+    options.finish()?;
+
+    let input = fs::read_to_string(filepath)?;
+    solver.run(&input)
+}
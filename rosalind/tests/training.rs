@@ -0,0 +1,94 @@
+//! Runs every solver against the Rosalind sample dataset in
+//!  `data/<ID>/training.txt` and checks the answer against
+//!  `data/<ID>/training_output.txt`
+
+use std::fs;
+use std::path::PathBuf;
+
+use rosalind_core::Options;
+
+fn data_dir(id: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("data")
+        .join(id)
+}
+
+fn check_training(id: &str) {
+    check_training_with(id, &[]);
+}
+
+/// Like `check_training`, but configuring the solver with `args` first
+fn check_training_with(id: &str, args: &[String]) {
+    let mut solver = rosalind::find_solver(id).expect("solver is registered");
+    let mut options = Options::parse(args).unwrap();
+    solver.configure(&mut options).unwrap();
+    options.finish().unwrap();
+    let dir = data_dir(id);
+    let input = fs::read_to_string(dir.join("training.txt")).expect("sample dataset exists");
+    let expected =
+        fs::read_to_string(dir.join("training_output.txt")).expect("sample output exists");
+
+    let actual = solver.run(&input).unwrap();
+    let accepted = solver.check(&input, &expected, &actual).unwrap();
+    assert!(
+        accepted,
+        "{} training answer rejected\nexpected:\n{}\nactual:\n{}",
+        id,
+        expected.trim(),
+        actual
+    );
+}
+
+macro_rules! training_tests {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                check_training($id);
+            }
+        )*
+    };
+}
+
+training_tests! {
+    dbru => "DBRU",
+    dna => "DNA",
+    fib => "FIB",
+    fibd => "FIBD",
+    gasm => "GASM",
+    grph => "GRPH",
+    iev => "IEV",
+    lexf => "LEXF",
+    lgis => "LGIS",
+    long => "LONG",
+    pcov => "PCOV",
+    perm => "PERM",
+    pmch => "PMCH",
+    sign => "SIGN",
+    splc => "SPLC",
+}
+
+/// Reads the sample proteins from `data/MPRT` rather than UniProt
+#[test]
+fn mprt() {
+    let local = data_dir("MPRT").display().to_string();
+    check_training_with("MPRT", &["--local".to_string(), local]);
+}
+
+#[test]
+fn every_solver_has_sample_data() {
+    for solver in rosalind::solvers() {
+        let dir = data_dir(solver.id());
+        assert!(
+            dir.join("training.txt").exists(),
+            "{} has no sample dataset",
+            solver.id()
+        );
+        assert!(
+            dir.join("training_output.txt").exists(),
+            "{} has no sample output",
+            solver.id()
+        );
+    }
+}